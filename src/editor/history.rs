@@ -0,0 +1,58 @@
+use crate::level::{Bridge, Coordinates, Level};
+
+/// The parts of a level that the editor changes
+#[derive(Debug, Clone)]
+pub(super) struct Snapshot {
+    vertices: Vec<Coordinates>,
+    bridge: Bridge,
+}
+
+impl Snapshot {
+    pub(super) fn new(level: &Level) -> Self {
+        Snapshot {
+            vertices: level.vertices.clone(),
+            bridge: level.bridge.clone(),
+        }
+    }
+
+    /// Put the level back in the state of the snapshot, returning the state it was in
+    fn restore(self, level: &mut Level) -> Snapshot {
+        Snapshot {
+            vertices: std::mem::replace(&mut level.vertices, self.vertices),
+            bridge: std::mem::replace(&mut level.bridge, self.bridge),
+        }
+    }
+}
+
+/// The states of a level before every edit, so that the edits can be undone and redone
+#[derive(Debug, Default)]
+pub(super) struct History {
+    undo_stack: Vec<Snapshot>,
+    redo_stack: Vec<Snapshot>,
+}
+
+impl History {
+    /// Remember the state of a level before an edit, which makes the edits that were undone unavailable
+    pub(super) fn push(&mut self, snapshot: Snapshot) {
+        self.undo_stack.push(snapshot);
+        self.redo_stack.clear();
+    }
+
+    /// Returns whether there was an edit to undo
+    pub(super) fn undo(&mut self, level: &mut Level) -> bool {
+        let Some(snapshot) = self.undo_stack.pop() else {
+            return false;
+        };
+        self.redo_stack.push(snapshot.restore(level));
+        true
+    }
+
+    /// Returns whether there was an undone edit to redo
+    pub(super) fn redo(&mut self, level: &mut Level) -> bool {
+        let Some(snapshot) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.undo_stack.push(snapshot.restore(level));
+        true
+    }
+}
@@ -0,0 +1,318 @@
+#[cfg(test)]
+mod unittest;
+
+mod history;
+
+use crate::level::{Bridge, Coordinates, Edge, Level, VertexIndex};
+use crate::material::{Material, MaterialTable};
+use anyhow::{anyhow, Result};
+use history::{History, Snapshot};
+
+/// The distance between the grid points the cursor snaps to
+pub const GRID_SIZE: f64 = 0.1;
+
+/// How close the cursor needs to be to a vertex or member to pick it
+const PICK_DISTANCE: f64 = GRID_SIZE / 2.0;
+
+/// A member of the bridge, identified by its material and its position in that material's list
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct MemberIndex(Material, usize);
+
+/// Builds the player's bridge on top of a level.
+/// The vertices and members of the level itself can be built upon, but not removed.
+pub struct Editor {
+    /// Always on a grid point
+    cursor: Coordinates,
+    /// The vertex the next member starts at
+    selected_vertex: Option<VertexIndex>,
+    material: Material,
+    level_vertex_count: usize,
+    level_bridge: Bridge,
+    history: History,
+}
+
+impl Editor {
+    /// Create an editor for a level without any of the player's vertices or members
+    pub fn new(level: &Level) -> Self {
+        let materials = &level.materials;
+        Editor {
+            cursor: Coordinates { x: 0.0, y: 0.0 },
+            selected_vertex: None,
+            material: Material::ALL
+                .into_iter()
+                .find(|material| materials.get(*material).is_allowed)
+                .unwrap_or(Material::Road),
+            level_vertex_count: level.vertices.len(),
+            level_bridge: level.bridge.clone(),
+            history: History::default(),
+        }
+    }
+
+    pub fn cursor(&self) -> &Coordinates {
+        &self.cursor
+    }
+
+    pub fn selected_vertex(&self) -> Option<&VertexIndex> {
+        self.selected_vertex.as_ref()
+    }
+
+    pub fn material(&self) -> Material {
+        self.material
+    }
+
+    /// Move the cursor by a number of grid points
+    pub fn move_cursor(&mut self, dx: i32, dy: i32) {
+        self.set_cursor(Coordinates {
+            x: self.cursor.x + dx as f64 * GRID_SIZE,
+            y: self.cursor.y + dy as f64 * GRID_SIZE,
+        });
+    }
+
+    /// Move the cursor to the grid point nearest to the position
+    pub fn set_cursor(&mut self, position: Coordinates) {
+        self.cursor = Coordinates {
+            x: (position.x / GRID_SIZE).round() * GRID_SIZE,
+            y: (position.y / GRID_SIZE).round() * GRID_SIZE,
+        };
+    }
+
+    /// Switch to the next material the level allows
+    pub fn next_material(&mut self, materials: &MaterialTable) {
+        let index = Material::ALL
+            .iter()
+            .position(|material| *material == self.material)
+            .unwrap_or_default();
+        if let Some(material) = (1..=Material::ALL.len())
+            .map(|offset| Material::ALL[(index + offset) % Material::ALL.len()])
+            .find(|material| materials.get(*material).is_allowed)
+        {
+            self.material = material;
+        }
+    }
+
+    /// The vertex under the cursor
+    pub fn hovered_vertex(&self, level: &Level) -> Option<VertexIndex> {
+        self.vertex_at_cursor(level)
+    }
+
+    /// The member under the cursor, when there is no vertex under it
+    pub fn hovered_member<'a>(&self, level: &'a Level) -> Option<&'a Edge> {
+        if self.vertex_at_cursor(level).is_some() {
+            return None;
+        }
+        self.member_at_cursor(level)
+            .map(|member| &level.bridge.members(member.0)[member.1])
+    }
+
+    /// Stop building from the selected vertex
+    pub fn cancel(&mut self) {
+        self.selected_vertex = None;
+    }
+
+    /// Select the vertex under the cursor, creating it if there is none.
+    /// When a vertex was already selected, a member is built between the two.
+    /// Returns whether the level changed.
+    pub fn place(&mut self, level: &mut Level) -> Result<bool> {
+        self.record(level, Self::place_unrecorded)
+    }
+
+    /// Select the vertex under the cursor to start a new member from, creating it if there is none.
+    /// Unlike placing, this never builds a member from the vertex that was selected before.
+    /// Returns whether the level changed.
+    pub fn start_member(&mut self, level: &mut Level) -> Result<bool> {
+        self.selected_vertex = None;
+        self.place(level)
+    }
+
+    /// Remove the player's vertex or member under the cursor, a vertex is removed along with its members.
+    /// Returns whether the level changed.
+    pub fn delete(&mut self, level: &mut Level) -> Result<bool> {
+        self.record(level, Self::delete_unrecorded)
+    }
+
+    /// Rebuild the player's member under the cursor from the selected material.
+    /// Returns whether the level changed.
+    pub fn change_material(&mut self, level: &mut Level) -> Result<bool> {
+        self.record(level, Self::change_material_unrecorded)
+    }
+
+    /// Revert the last edit, returns whether there was one
+    pub fn undo(&mut self, level: &mut Level) -> bool {
+        // The selected vertex might not exist anymore
+        self.selected_vertex = None;
+        self.history.undo(level)
+    }
+
+    /// Apply the last edit that was undone again, returns whether there was one
+    pub fn redo(&mut self, level: &mut Level) -> bool {
+        self.selected_vertex = None;
+        self.history.redo(level)
+    }
+
+    /// Apply an edit, and add it to the history when it changed the level
+    fn record(
+        &mut self,
+        level: &mut Level,
+        edit: fn(&mut Self, &mut Level) -> Result<bool>,
+    ) -> Result<bool> {
+        let snapshot = Snapshot::new(level);
+        let is_changed = edit(self, level)?;
+        if is_changed {
+            self.history.push(snapshot);
+        }
+        Ok(is_changed)
+    }
+
+    fn place_unrecorded(&mut self, level: &mut Level) -> Result<bool> {
+        let vertex = self.vertex_at_cursor(level);
+        let Some(start) = self.selected_vertex.clone() else {
+            let is_new = vertex.is_none();
+            self.selected_vertex =
+                Some(vertex.unwrap_or_else(|| Self::add_vertex(level, self.cursor.clone())));
+            return Ok(is_new);
+        };
+        if vertex.as_ref() == Some(&start) {
+            self.selected_vertex = None;
+            return Ok(false);
+        }
+
+        let end_position = match &vertex {
+            Some(vertex) => level.vertices[vertex.0].clone(),
+            None => self.cursor.clone(),
+        };
+        let start_position = &level.vertices[start.0];
+        let length = (end_position.x - start_position.x).hypot(end_position.y - start_position.y);
+        level.materials.get(self.material).check_buildable(length)?;
+        if let Some(vertex) = &vertex {
+            if Self::find_member(level, &start, vertex).is_some() {
+                return Err(anyhow!("These vertices are already connected"));
+            }
+        }
+
+        let end = match vertex {
+            Some(vertex) => vertex,
+            None => Self::add_vertex(level, end_position),
+        };
+        level
+            .bridge
+            .members_mut(self.material)
+            .push(Edge(start, end.clone()));
+        // Keep building from the end of the new member
+        self.selected_vertex = Some(end);
+        Ok(true)
+    }
+
+    fn delete_unrecorded(&mut self, level: &mut Level) -> Result<bool> {
+        if let Some(vertex) = self.vertex_at_cursor(level) {
+            if vertex.0 < self.level_vertex_count {
+                return Err(anyhow!("Vertices of the level can't be removed"));
+            }
+            self.remove_vertex(level, vertex);
+            return Ok(true);
+        }
+        if let Some(member) = self.member_at_cursor(level) {
+            if self.is_level_member(member) {
+                return Err(anyhow!("Members of the level can't be removed"));
+            }
+            level.bridge.members_mut(member.0).remove(member.1);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn change_material_unrecorded(&mut self, level: &mut Level) -> Result<bool> {
+        let Some(member) = self.member_at_cursor(level) else {
+            return Ok(false);
+        };
+        if member.0 == self.material {
+            return Ok(false);
+        }
+        if self.is_level_member(member) {
+            return Err(anyhow!("Members of the level can't be changed"));
+        }
+
+        let edge = level.bridge.members(member.0)[member.1].clone();
+        let (start, end) = (&level.vertices[edge.0 .0], &level.vertices[edge.1 .0]);
+        level
+            .materials
+            .get(self.material)
+            .check_buildable((end.x - start.x).hypot(end.y - start.y))?;
+        level.bridge.members_mut(member.0).remove(member.1);
+        level.bridge.members_mut(self.material).push(edge);
+        Ok(true)
+    }
+
+    fn add_vertex(level: &mut Level, position: Coordinates) -> VertexIndex {
+        level.vertices.push(position);
+        VertexIndex(level.vertices.len() - 1)
+    }
+
+    /// Remove a player's vertex and its members, the indices of the vertices after it shift down
+    fn remove_vertex(&mut self, level: &mut Level, vertex: VertexIndex) {
+        level.vertices.remove(vertex.0);
+        for material in Material::ALL {
+            let members = level.bridge.members_mut(material);
+            members.retain(|edge| edge.0 != vertex && edge.1 != vertex);
+            for edge in members {
+                for index in [&mut edge.0, &mut edge.1] {
+                    if index.0 > vertex.0 {
+                        index.0 -= 1;
+                    }
+                }
+            }
+        }
+        self.selected_vertex = match self.selected_vertex.take() {
+            Some(selected) if selected == vertex => None,
+            Some(selected) if selected.0 > vertex.0 => Some(VertexIndex(selected.0 - 1)),
+            selected => selected,
+        };
+    }
+
+    fn is_level_member(&self, member: MemberIndex) -> bool {
+        member.1 < self.level_bridge.members(member.0).len()
+    }
+
+    fn vertex_at_cursor(&self, level: &Level) -> Option<VertexIndex> {
+        level
+            .vertices
+            .iter()
+            .position(|vertex| {
+                (vertex.x - self.cursor.x).hypot(vertex.y - self.cursor.y) <= PICK_DISTANCE
+            })
+            .map(VertexIndex)
+    }
+
+    /// Find the member closest to the cursor, preferring the ones built last
+    fn member_at_cursor(&self, level: &Level) -> Option<MemberIndex> {
+        let mut closest: Option<(f64, MemberIndex)> = None;
+        for material in Material::ALL {
+            for (index, edge) in level.bridge.members(material).iter().enumerate() {
+                let (Some(start), Some(end)) =
+                    (level.vertices.get(edge.0 .0), level.vertices.get(edge.1 .0))
+                else {
+                    continue;
+                };
+                let distance = self.cursor.distance_to_segment(start, end);
+                if distance <= PICK_DISTANCE
+                    && closest.is_none_or(|(closest_distance, _)| distance <= closest_distance)
+                {
+                    closest = Some((distance, MemberIndex(material, index)));
+                }
+            }
+        }
+        closest.map(|(_, member)| member)
+    }
+
+    fn find_member(level: &Level, start: &VertexIndex, end: &VertexIndex) -> Option<MemberIndex> {
+        Material::ALL.into_iter().find_map(|material| {
+            level
+                .bridge
+                .members(material)
+                .iter()
+                .position(|edge| {
+                    (&edge.0 == start && &edge.1 == end) || (&edge.0 == end && &edge.1 == start)
+                })
+                .map(|index| MemberIndex(material, index))
+        })
+    }
+}
@@ -0,0 +1,249 @@
+use crate::editor::Editor;
+use crate::level::{Bridge, Coordinates, Edge, Level, VertexIndex};
+use crate::material::Material;
+
+/// A level with a road from x 0 to x 1, and a gap up to x 3
+fn example_level() -> Level {
+    Level {
+        vertices: vec![
+            Coordinates { x: 0.0, y: 1.0 },
+            Coordinates { x: 1.0, y: 1.0 },
+            Coordinates { x: 3.0, y: 1.0 },
+        ],
+        anchors: vec![VertexIndex(0), VertexIndex(1), VertexIndex(2)],
+        bridge: Bridge {
+            road: vec![Edge(VertexIndex(0), VertexIndex(1))],
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn cursor_snaps_to_grid_test() {
+    let mut editor = Editor::new(&example_level());
+    editor.set_cursor(Coordinates { x: 1.04, y: 0.96 });
+    assert!((editor.cursor().x - 1.0).abs() < 1e-9);
+    assert!((editor.cursor().y - 1.0).abs() < 1e-9);
+    editor.move_cursor(3, -2);
+    assert!((editor.cursor().x - 1.3).abs() < 1e-9);
+    assert!((editor.cursor().y - 0.8).abs() < 1e-9);
+}
+
+#[test]
+fn place_members_test() {
+    let mut level = example_level();
+    let mut editor = Editor::new(&level);
+    assert_eq!(editor.material(), Material::Road);
+
+    // Selecting an existing vertex doesn't change the level
+    editor.set_cursor(Coordinates { x: 1.0, y: 1.0 });
+    assert!(!editor.place(&mut level).unwrap());
+    assert_eq!(editor.selected_vertex(), Some(&VertexIndex(1)));
+
+    // Placing on empty space creates a vertex, and continues building from it
+    editor.set_cursor(Coordinates { x: 2.0, y: 1.0 });
+    assert!(editor.place(&mut level).unwrap());
+    assert_eq!(level.vertices.len(), 4);
+    assert_eq!(editor.selected_vertex(), Some(&VertexIndex(3)));
+    editor.set_cursor(Coordinates { x: 3.0, y: 1.0 });
+    assert!(editor.place(&mut level).unwrap());
+    assert_eq!(
+        level.bridge.road,
+        vec![
+            Edge(VertexIndex(0), VertexIndex(1)),
+            Edge(VertexIndex(1), VertexIndex(3)),
+            Edge(VertexIndex(3), VertexIndex(2)),
+        ]
+    );
+
+    // Vertices can only be connected once
+    editor.cancel();
+    editor.set_cursor(Coordinates { x: 2.0, y: 1.0 });
+    editor.place(&mut level).unwrap();
+    editor.set_cursor(Coordinates { x: 1.0, y: 1.0 });
+    assert!(editor.place(&mut level).is_err());
+    assert_eq!(level.bridge.road.len(), 3);
+}
+
+#[test]
+fn start_member_test() {
+    let mut level = example_level();
+    let mut editor = Editor::new(&level);
+    // Two drags in a row, as the mouse builds them: the first from 1 to 2, and the second from 2.5 to 3
+    for (start, end) in [(1.0, 2.0), (2.5, 3.0)] {
+        editor.set_cursor(Coordinates { x: start, y: 1.0 });
+        editor.start_member(&mut level).unwrap();
+        editor.set_cursor(Coordinates { x: end, y: 1.0 });
+        editor.place(&mut level).unwrap();
+    }
+    assert_eq!(
+        level.bridge.road,
+        vec![
+            Edge(VertexIndex(0), VertexIndex(1)),
+            Edge(VertexIndex(1), VertexIndex(3)),
+            Edge(VertexIndex(4), VertexIndex(2)),
+        ]
+    );
+
+    // A drag can start at the end of the previous member
+    editor.set_cursor(Coordinates { x: 3.0, y: 1.0 });
+    editor.start_member(&mut level).unwrap();
+    assert_eq!(editor.selected_vertex(), Some(&VertexIndex(2)));
+    editor.set_cursor(Coordinates { x: 3.0, y: 2.0 });
+    assert!(editor.place(&mut level).unwrap());
+    assert_eq!(
+        level.bridge.road.last(),
+        Some(&Edge(VertexIndex(2), VertexIndex(5)))
+    );
+}
+
+#[test]
+fn place_respects_materials_test() {
+    let mut level = example_level();
+    level.materials.road.is_allowed = false;
+    let mut editor = Editor::new(&level);
+    assert_eq!(editor.material(), Material::Wood);
+
+    // Forbidden materials are skipped
+    level.materials.steel.is_allowed = false;
+    editor.next_material(&level.materials);
+    assert_eq!(editor.material(), Material::Wire);
+    editor.next_material(&level.materials);
+    assert_eq!(editor.material(), Material::Wood);
+
+    // Members can't be longer than the material's maximum length, and nothing is changed when they are
+    editor.set_cursor(Coordinates { x: 0.0, y: 1.0 });
+    editor.place(&mut level).unwrap();
+    editor.set_cursor(Coordinates { x: 3.0, y: 1.0 });
+    assert!(editor.place(&mut level).is_err());
+    assert_eq!(level.vertices.len(), 3);
+    assert!(level.bridge.wood.is_empty());
+}
+
+#[test]
+fn delete_test() {
+    let mut level = example_level();
+    let mut editor = Editor::new(&level);
+    for (x, y) in [(1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (3.0, 1.0)] {
+        editor.set_cursor(Coordinates { x, y });
+        editor.place(&mut level).unwrap();
+    }
+    assert_eq!(level.vertices.len(), 5);
+    assert_eq!(level.bridge.road.len(), 4);
+
+    // Removing a vertex removes its members, and shifts the vertices after it
+    editor.set_cursor(Coordinates { x: 2.0, y: 1.0 });
+    assert!(editor.delete(&mut level).unwrap());
+    assert_eq!(level.vertices.len(), 4);
+    assert_eq!(
+        level.bridge.road,
+        vec![
+            Edge(VertexIndex(0), VertexIndex(1)),
+            Edge(VertexIndex(3), VertexIndex(2)),
+        ]
+    );
+    assert_eq!(editor.selected_vertex(), Some(&VertexIndex(2)));
+
+    // Members are removed by pointing anywhere along them
+    editor.set_cursor(Coordinates { x: 2.5, y: 1.5 });
+    assert!(editor.delete(&mut level).unwrap());
+    assert_eq!(
+        level.bridge.road,
+        vec![Edge(VertexIndex(0), VertexIndex(1))]
+    );
+
+    // The level's own vertices and members stay
+    editor.set_cursor(Coordinates { x: 0.0, y: 1.0 });
+    assert!(editor.delete(&mut level).is_err());
+    editor.set_cursor(Coordinates { x: 0.5, y: 1.0 });
+    assert!(editor.delete(&mut level).is_err());
+    editor.set_cursor(Coordinates { x: 0.5, y: 0.5 });
+    assert!(!editor.delete(&mut level).unwrap());
+    assert_eq!(level.bridge.road.len(), 1);
+}
+
+#[test]
+fn change_material_test() {
+    let mut level = example_level();
+    let mut editor = Editor::new(&level);
+    editor.set_cursor(Coordinates { x: 1.0, y: 1.0 });
+    editor.place(&mut level).unwrap();
+    editor.set_cursor(Coordinates { x: 2.0, y: 1.0 });
+    editor.place(&mut level).unwrap();
+
+    editor.next_material(&level.materials);
+    assert_eq!(editor.material(), Material::Wood);
+    editor.set_cursor(Coordinates { x: 1.5, y: 1.0 });
+    assert!(editor.change_material(&mut level).unwrap());
+    assert_eq!(level.bridge.road.len(), 1);
+    assert_eq!(
+        level.bridge.wood,
+        vec![Edge(VertexIndex(1), VertexIndex(3))]
+    );
+
+    // The level's own members can't be changed
+    editor.set_cursor(Coordinates { x: 0.5, y: 1.0 });
+    assert!(editor.change_material(&mut level).is_err());
+}
+
+#[test]
+fn hover_test() {
+    let mut level = example_level();
+    let mut editor = Editor::new(&level);
+    editor.set_cursor(Coordinates { x: 1.0, y: 1.0 });
+    editor.place(&mut level).unwrap();
+    editor.set_cursor(Coordinates { x: 2.0, y: 1.0 });
+    editor.place(&mut level).unwrap();
+
+    // Vertices take precedence over the members attached to them
+    assert_eq!(editor.hovered_vertex(&level), Some(VertexIndex(3)));
+    assert_eq!(editor.hovered_member(&level), None);
+    editor.set_cursor(Coordinates { x: 1.5, y: 1.0 });
+    assert_eq!(editor.hovered_vertex(&level), None);
+    assert_eq!(
+        editor.hovered_member(&level),
+        Some(&Edge(VertexIndex(1), VertexIndex(3)))
+    );
+    editor.set_cursor(Coordinates { x: 1.5, y: 1.5 });
+    assert_eq!(editor.hovered_member(&level), None);
+}
+
+#[test]
+fn undo_redo_test() {
+    let pristine_level = example_level();
+    let mut level = pristine_level.clone();
+    let mut editor = Editor::new(&level);
+    assert!(!editor.undo(&mut level));
+
+    // Selecting doesn't add to the history, building does
+    editor.set_cursor(Coordinates { x: 1.0, y: 1.0 });
+    editor.place(&mut level).unwrap();
+    editor.set_cursor(Coordinates { x: 2.0, y: 1.0 });
+    editor.place(&mut level).unwrap();
+    let built_level = level.clone();
+    editor.next_material(&level.materials);
+    editor.set_cursor(Coordinates { x: 1.5, y: 1.0 });
+    editor.change_material(&mut level).unwrap();
+    let changed_level = level.clone();
+
+    assert!(editor.undo(&mut level));
+    assert_eq!(level, built_level);
+    assert_eq!(editor.selected_vertex(), None);
+    assert!(editor.undo(&mut level));
+    assert_eq!(level, pristine_level);
+    assert!(!editor.undo(&mut level));
+    assert!(editor.redo(&mut level));
+    assert!(editor.redo(&mut level));
+    assert_eq!(level, changed_level);
+    assert!(!editor.redo(&mut level));
+
+    // A new edit replaces the edits that were undone
+    editor.undo(&mut level);
+    editor.set_cursor(Coordinates { x: 2.0, y: 1.0 });
+    editor.delete(&mut level).unwrap();
+    assert_eq!(level, pristine_level);
+    assert!(!editor.redo(&mut level));
+    assert!(editor.undo(&mut level));
+    assert_eq!(level, built_level);
+}
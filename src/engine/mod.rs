@@ -1,50 +1,243 @@
 #[cfg(test)]
 mod unittest;
 
-use crate::level::Level;
+mod terrain;
+mod truss;
+mod vehicle;
+
+use crate::level::{Edge, Goal, Level, VertexIndex};
 use crate::scene::{Object, Scene};
-use anyhow::{ensure, Result};
+use anyhow::{ensure, Context, Result};
+use std::time::Duration;
+use terrain::Terrain;
+pub use truss::MemberStress;
+use truss::Truss;
+use vehicle::{RoadSegment, Vehicle};
+
+type Vector = euclid::default::Vector2D<f64>;
+
+/// Gravitational acceleration in world units per second squared
+const GRAVITY: f64 = 9.81;
+
+/// The longest time a single scene tick may simulate in seconds, so a stalled UI doesn't cause a burst of steps
+const MAX_TICK_DURATION: f64 = 0.25;
+
+/// The lowest step rate at which the bridge members remain stable
+const MIN_STEP_RATE: u32 = 240;
+
+/// The score for completing a level, a point is added for every unit of budget left over
+const COMPLETION_SCORE: u32 = 1000;
+
+/// The settings that, together with the level, fully determine the outcome of a simulation
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct SimulationParameters {
+    /// The number of physics steps per simulated second
+    pub step_rate: u32,
+}
+
+impl Default for SimulationParameters {
+    fn default() -> Self {
+        SimulationParameters { step_rate: 480 }
+    }
+}
+
+impl SimulationParameters {
+    /// The duration of a single physics step, in seconds
+    pub fn time_step(&self) -> f64 {
+        1.0 / self.step_rate as f64
+    }
+}
+
+/// Something noteworthy that happened during a simulation tick
+#[derive(Debug)]
+pub enum SimulationEvent {
+    /// A member was overloaded and removed from the simulation, as it was just before breaking
+    MemberBroken(Object),
+}
+
+/// Why a simulation failed
+#[derive(Debug, Clone, PartialEq)]
+pub enum FailureReason {
+    /// A vehicle fell below the water level
+    VehicleDrowned,
+    /// The vehicles did not reach the goal in time
+    Timeout,
+}
+
+/// Whether the vehicles made it to the goal
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Success,
+    Failure(FailureReason),
+}
+
+/// Measurements collected while simulating
+#[derive(Debug, Clone, Default)]
+pub struct SimulationStatistics {
+    /// The simulated time in seconds
+    pub elapsed_time: f64,
+    pub broken_member_count: usize,
+    /// The highest load ratio any member has reached
+    pub max_load_ratio: f64,
+}
+
+/// The outcome of a completed simulation
+#[derive(Debug, Clone)]
+pub struct SimulationResult {
+    pub outcome: Outcome,
+    pub statistics: SimulationStatistics,
+    /// The construction cost of the bridge that was simulated
+    pub cost: f64,
+    pub budget: Option<f64>,
+    /// Zero when the level failed, cheaper bridges score higher
+    pub score: u32,
+}
 
 pub enum SimulationState {
     Paused,
     Running,
-    _Completed,
+    Completed(SimulationResult),
     Cleared,
 }
 
+/// Everything that moves during a simulation
+#[derive(Clone, Default)]
+struct World {
+    truss: Truss,
+    vehicles: Vec<Vehicle>,
+    statistics: SimulationStatistics,
+    /// The total time the scene ticks asked to simulate, in seconds
+    requested_time: f64,
+    step_count: u64,
+}
+
 pub struct Engine {
     level: Level,
+    parameters: SimulationParameters,
     static_scene: Scene,
     simulation_scene: Scene,
     simulation_state: SimulationState,
+    static_road: Vec<RoadSegment>,
+    terrain: Terrain,
+    static_world: World,
+    simulation_world: World,
 }
 
 impl Engine {
     pub(crate) fn new() -> Engine {
         Engine {
             level: Level::default(),
+            parameters: SimulationParameters::default(),
             static_scene: Scene::default(),
             simulation_scene: Scene::default(),
             simulation_state: SimulationState::Cleared,
+            static_road: vec![],
+            terrain: Terrain::default(),
+            static_world: World::default(),
+            simulation_world: World::default(),
         }
     }
 
     pub fn set_level(&mut self, new_level: Level) -> Result<()> {
         self.level = new_level;
         self.static_scene = Scene::new(&self.level)?;
+        self.static_road = self
+            .level
+            .road
+            .iter()
+            .map(|edge| {
+                Ok(RoadSegment {
+                    start: self.vertex_position(&edge.0)?,
+                    end: self.vertex_position(&edge.1)?,
+                    joints: None,
+                })
+            })
+            .collect::<Result<Vec<RoadSegment>>>()?;
+        self.terrain = Terrain::new(&self.level.background, self.level.failure.water_level);
+        self.static_world = World {
+            truss: Truss::new(&self.level)?,
+            vehicles: self
+                .static_scene
+                .0
+                .iter()
+                .filter_map(|object| match object {
+                    Object::Vehicle(vehicle) => Some(Vehicle::new(vehicle)),
+                    _ => None,
+                })
+                .collect(),
+            ..Default::default()
+        };
 
         // TODO (Menno 26.12.2022) This copy should be done at simulation start instead,
         //  once world menu has been created
         self.simulation_scene = self.static_scene.clone();
+        self.simulation_world = self.static_world.clone();
         Ok(())
     }
 
+    /// Change the simulation parameters, which resets the simulation
+    pub fn set_simulation_parameters(&mut self, parameters: SimulationParameters) -> Result<()> {
+        ensure!(
+            parameters.step_rate >= MIN_STEP_RATE,
+            "Step rate should be at least {}, instead found {}",
+            MIN_STEP_RATE,
+            parameters.step_rate
+        );
+        self.parameters = parameters;
+        self.reset_simulation();
+        Ok(())
+    }
+
+    pub fn simulation_parameters(&self) -> &SimulationParameters {
+        &self.parameters
+    }
+
+    /// The level being simulated, including the player's bridge
+    pub fn level(&self) -> &Level {
+        &self.level
+    }
+
     pub fn get_scene(&self) -> &Scene {
         &self.simulation_scene
     }
 
-    pub fn start_simulation(&mut self) {
+    /// The construction cost of the bridge, based on the length and material of every member
+    pub fn bridge_cost(&self) -> f64 {
+        self.level
+            .materials
+            .iter()
+            .map(|(material, properties)| {
+                self.level
+                    .bridge
+                    .members(material)
+                    .iter()
+                    .map(|edge| self.edge_length(edge))
+                    .sum::<f64>()
+                    * properties.cost_per_length
+            })
+            // Summing starts at negative zero, which would be printed as -0 for an empty bridge
+            .fold(0.0, |total, cost| total + cost)
+    }
+
+    /// The most the bridge may cost, or none if it may cost anything
+    pub fn budget(&self) -> Option<f64> {
+        self.level.budget
+    }
+
+    pub fn is_over_budget(&self) -> bool {
+        matches!(self.budget(), Some(budget) if self.bridge_cost() > budget)
+    }
+
+    /// Start or continue the simulation, which is refused when the bridge is over budget
+    pub fn start_simulation(&mut self) -> Result<()> {
+        ensure!(
+            !self.is_over_budget(),
+            "The bridge costs {:.0}, which is over the budget of {:.0}",
+            self.bridge_cost(),
+            self.budget().unwrap_or_default()
+        );
         self.simulation_state = SimulationState::Running;
+        Ok(())
     }
 
     pub fn pause_simulation(&mut self) {
@@ -53,6 +246,7 @@ impl Engine {
 
     pub fn reset_simulation(&mut self) {
         self.simulation_scene = self.static_scene.clone();
+        self.simulation_world = self.static_world.clone();
         self.simulation_state = SimulationState::Cleared;
     }
 
@@ -60,32 +254,199 @@ impl Engine {
         &self.simulation_state
     }
 
-    pub fn simulate_scene_tick(&mut self) -> Result<()> {
+    /// Measurements of the simulation so far
+    pub fn statistics(&self) -> &SimulationStatistics {
+        &self.simulation_world.statistics
+    }
+
+    /// The highest load every bridge member carried during the simulation so far
+    pub fn member_stresses(&self) -> Vec<MemberStress> {
+        self.simulation_world.truss.member_stresses()
+    }
+
+    /// The number of physics steps that have been simulated since the simulation was reset
+    pub fn step_count(&self) -> u64 {
+        self.simulation_world.step_count
+    }
+
+    /// Advance the simulation by the time that passed since the previous tick.
+    /// The time is simulated in fixed physics steps, any remainder is carried over to the next tick.
+    pub fn simulate_scene_tick(&mut self, tick_duration: Duration) -> Result<Vec<SimulationEvent>> {
         ensure!(matches!(self.simulation_state, SimulationState::Running));
-        self.the_fancy_math();
-        Ok(())
+        let time_step = self.parameters.time_step();
+        let world = &mut self.simulation_world;
+        world.requested_time += tick_duration.as_secs_f64();
+
+        // The small margin prevents rounding errors from postponing a step to the next tick
+        let requested_steps = (world.requested_time / time_step + 1e-6).floor() as u64;
+        let max_steps = (MAX_TICK_DURATION / time_step).round() as u64;
+        let mut steps = requested_steps.saturating_sub(world.step_count);
+        if steps > max_steps {
+            steps = max_steps;
+            world.requested_time = (world.step_count + max_steps) as f64 * time_step;
+        }
+
+        let events = self.simulate_steps(steps);
+        self.update_simulation_scene();
+        Ok(events)
     }
 
-    fn the_fancy_math(&mut self) {
-        const GRAVITY: f64 = 0.1;
-        for object in &mut self.simulation_scene.0 {
-            match object {
-                Object::Wire(wire) => {
-                    wire.line.0.y -= GRAVITY;
-                    wire.line.1.y -= GRAVITY;
-                }
-                Object::Beam(beam) => {
-                    if !beam.is_static {
-                        beam.line.0.y -= GRAVITY;
-                        beam.line.1.y -= GRAVITY;
+    /// Jump to the specified physics step, seeking backwards replays the simulation from the start.
+    /// The simulation is paused afterwards, unless it completed before reaching the step.
+    pub fn seek(&mut self, target_step: u64) -> Result<Vec<SimulationEvent>> {
+        if target_step < self.simulation_world.step_count {
+            self.simulation_world = self.static_world.clone();
+        } else if matches!(self.simulation_state, SimulationState::Completed(_)) {
+            return Ok(vec![]);
+        }
+        self.simulation_state = SimulationState::Paused;
+
+        let events = self.simulate_steps(target_step - self.simulation_world.step_count);
+        let world = &mut self.simulation_world;
+        world.requested_time = world.step_count as f64 * self.parameters.time_step();
+        self.update_simulation_scene();
+        Ok(events)
+    }
+
+    /// Perform physics steps until the specified number of steps is reached or the simulation completes
+    fn simulate_steps(&mut self, steps: u64) -> Vec<SimulationEvent> {
+        let time_step = self.parameters.time_step();
+        let mut events: Vec<SimulationEvent> = vec![];
+        for _ in 0..steps {
+            events.append(&mut self.step(time_step));
+            if let Some(outcome) = self.check_outcome() {
+                let cost = self.bridge_cost();
+                self.simulation_state = SimulationState::Completed(SimulationResult {
+                    score: self.score(&outcome, cost),
+                    outcome,
+                    statistics: self.simulation_world.statistics.clone(),
+                    cost,
+                    budget: self.budget(),
+                });
+                break;
+            }
+        }
+        events
+    }
+
+    /// Advance the vehicles and the bridge they drive on by a single physics step
+    fn step(&mut self, time_step: f64) -> Vec<SimulationEvent> {
+        let world = &mut self.simulation_world;
+        let mut roads = self.static_road.clone();
+        roads.append(&mut world.truss.road_segments());
+
+        for vehicle in &mut world.vehicles {
+            for (joint, load) in vehicle.step(&roads, &self.terrain, time_step) {
+                world.truss.apply_load(joint, load);
+            }
+        }
+
+        let events: Vec<SimulationEvent> = world
+            .truss
+            .step(&self.terrain, time_step)
+            .into_iter()
+            .map(SimulationEvent::MemberBroken)
+            .collect();
+
+        world.step_count += 1;
+        let statistics = &mut world.statistics;
+        statistics.elapsed_time += time_step;
+        statistics.broken_member_count += events.len();
+        statistics.max_load_ratio = statistics.max_load_ratio.max(world.truss.max_load_ratio());
+        events
+    }
+
+    /// Check whether the vehicles have reached the goal, or failed to do so
+    fn check_outcome(&mut self) -> Option<Outcome> {
+        let world = &mut self.simulation_world;
+        if let Some(goal) = &self.level.goal {
+            for vehicle in &mut world.vehicles {
+                let position = vehicle.position();
+                let has_reached_goal = match goal {
+                    Goal::FinishLine(x) => position.x >= *x,
+                    Goal::Zone(min, max) => {
+                        (min.x..=max.x).contains(&position.x)
+                            && (min.y..=max.y).contains(&position.y)
                     }
-                }
-                Object::Vehicle(vehicle) => {
-                    vehicle.position.y -= GRAVITY;
-                    // hardcoded rotate by three degrees
-                    vehicle.rotation -= 3.0;
+                };
+                if has_reached_goal {
+                    vehicle.finish();
                 }
             }
         }
+
+        let failure = &self.level.failure;
+        let has_drowned = world
+            .vehicles
+            .iter()
+            .any(|vehicle| !vehicle.has_finished() && vehicle.is_sunk());
+        if has_drowned {
+            return Some(Outcome::Failure(FailureReason::VehicleDrowned));
+        }
+
+        if self.level.goal.is_some() && world.vehicles.iter().all(|vehicle| vehicle.has_finished())
+        {
+            return Some(Outcome::Success);
+        }
+
+        match failure.timeout {
+            Some(timeout) if world.statistics.elapsed_time >= timeout => {
+                Some(Outcome::Failure(FailureReason::Timeout))
+            }
+            _ => None,
+        }
+    }
+
+    fn score(&self, outcome: &Outcome, cost: f64) -> u32 {
+        match outcome {
+            Outcome::Success => {
+                let remaining_budget = self.budget().map_or(0.0, |budget| budget - cost);
+                COMPLETION_SCORE + remaining_budget.max(0.0).floor() as u32
+            }
+            Outcome::Failure(_) => 0,
+        }
+    }
+
+    fn vertex_position(&self, index: &VertexIndex) -> Result<Vector> {
+        let vertex = self.level.vertices.get(index.0).with_context(|| {
+            format!(
+                "Could not find vertex {}, the level has {} vertices",
+                index.0,
+                self.level.vertices.len()
+            )
+        })?;
+        Ok(Vector::new(vertex.x, vertex.y))
+    }
+
+    fn edge_length(&self, edge: &Edge) -> f64 {
+        let vertices = &self.level.vertices;
+        match (vertices.get(edge.0 .0), vertices.get(edge.1 .0)) {
+            (Some(a), Some(b)) => (b.x - a.x).hypot(b.y - a.y),
+            _ => 0.0,
+        }
+    }
+
+    /// Regenerate the simulation scene from the background, static roads, simulated bridge members and vehicles
+    fn update_simulation_scene(&mut self) {
+        let mut objects: Vec<Object> = self
+            .static_scene
+            .0
+            .iter()
+            .filter(|object| match object {
+                Object::Background(_) => true,
+                Object::Beam(beam) => beam.is_static,
+                _ => false,
+            })
+            .copied()
+            .collect();
+        objects.append(&mut self.simulation_world.truss.scene_objects(&self.terrain));
+        objects.extend(
+            self.simulation_world
+                .vehicles
+                .iter()
+                .map(|vehicle| vehicle.scene_object()),
+        );
+        self.simulation_scene = Scene(objects);
+        self.simulation_scene.sort();
     }
 }
@@ -0,0 +1,118 @@
+use super::Vector;
+use crate::level::Background;
+
+/// The fraction of their velocity that objects in water lose per second
+const WATER_DRAG: f64 = 4.0;
+
+/// The fraction of their velocity along the ground that objects sliding over it lose per second
+const GROUND_FRICTION: f64 = 4.0;
+
+/// A straight piece of solid ground
+#[derive(Debug, Copy, Clone)]
+struct TerrainSegment {
+    start: Vector,
+    end: Vector,
+}
+
+impl TerrainSegment {
+    /// The normal of the segment that points away from the ground, which is upwards for non-vertical segments
+    fn normal(&self) -> Vector {
+        let delta = self.end - self.start;
+        if delta.x >= 0.0 {
+            Vector::new(-delta.y, delta.x)
+        } else {
+            Vector::new(delta.y, -delta.x)
+        }
+    }
+
+    /// Whether moving in a straight line from start to end crosses into the segment's ground side.
+    /// Moving out of the ground side is allowed, so objects can slide over the corner between two segments.
+    fn blocks(&self, start: Vector, end: Vector) -> bool {
+        let normal = self.normal();
+        let start_side = (start - self.start).dot(normal);
+        let end_side = (end - self.start).dot(normal);
+        // Points on the segment count as above it, so objects resting on the ground can move away from it
+        let is_entering = start_side >= 0.0 && end_side < 0.0;
+        if !is_entering {
+            return false;
+        }
+
+        let crossing = start + (end - start) * (start_side / (start_side - end_side));
+        let delta = self.end - self.start;
+        let fraction = (crossing - self.start).dot(delta) / delta.square_length();
+        (0.0..=1.0).contains(&fraction)
+    }
+}
+
+/// The ground and water of a level, which falling objects collide with or sink in.
+/// Vehicles only drive on roads, the ground just stops them from falling through it.
+#[derive(Debug, Clone, Default)]
+pub struct Terrain {
+    segments: Vec<TerrainSegment>,
+    water_level: Option<f64>,
+}
+
+impl Terrain {
+    /// Create the terrain of a level's background, with the level's failure water level,
+    /// so objects sink where vehicles fail
+    pub fn new(background: &Background, water_level: Option<f64>) -> Self {
+        Terrain {
+            segments: background
+                .terrain
+                .iter()
+                .flat_map(|polyline| {
+                    polyline.points.windows(2).map(|pair| TerrainSegment {
+                        start: Vector::new(pair[0].x, pair[0].y),
+                        end: Vector::new(pair[1].x, pair[1].y),
+                    })
+                })
+                .filter(|segment| (segment.end - segment.start).square_length() > 0.0)
+                .collect(),
+            water_level,
+        }
+    }
+
+    /// Whether moving in a straight line from start to end would pass through the ground
+    pub fn blocks(&self, start: Vector, end: Vector) -> bool {
+        self.segments
+            .iter()
+            .any(|segment| segment.blocks(start, end))
+    }
+
+    /// Stop the part of an object's velocity that moves it from start to end into the ground,
+    /// so it slides along the ground instead, slowed down by friction.
+    /// Returns whether the ground was in the way.
+    pub fn collide(
+        &self,
+        start: Vector,
+        end: Vector,
+        velocity: &mut Vector,
+        time_step: f64,
+    ) -> bool {
+        let Some(segment) = self
+            .segments
+            .iter()
+            .find(|segment| segment.blocks(start, end))
+        else {
+            return false;
+        };
+        let normal = segment.normal().normalize();
+        let normal_speed = velocity.dot(normal);
+        if normal_speed < 0.0 {
+            *velocity -= normal * normal_speed;
+        }
+        *velocity *= (1.0 - GROUND_FRICTION * time_step).max(0.0);
+        true
+    }
+
+    pub fn is_under_water(&self, point: Vector) -> bool {
+        matches!(self.water_level, Some(level) if point.y < level)
+    }
+
+    /// Slow down an object at the specified point when it is moving through water
+    pub fn apply_drag(&self, point: Vector, velocity: &mut Vector, time_step: f64) {
+        if self.is_under_water(point) {
+            *velocity *= (1.0 - WATER_DRAG * time_step).max(0.0);
+        }
+    }
+}
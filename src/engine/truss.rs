@@ -0,0 +1,423 @@
+use super::terrain::Terrain;
+use super::vehicle::RoadSegment;
+use super::{Vector, GRAVITY};
+use crate::level::{Edge, Level, VertexIndex};
+use crate::material::{Material, MaterialProperties};
+use crate::scene::{Beam, BeamMaterial, Coordinates, Line, Object, Wire, WireMaterial};
+use anyhow::{ensure, Context, Result};
+
+/// The mass of the connection plate at every joint that has members attached to it
+const JOINT_MASS: f64 = 0.1;
+
+/// The fraction of critical damping applied along each member
+const DAMPING_RATIO: f64 = 0.5;
+
+/// The highest natural frequency a member may have, in radians per second.
+/// Short and light members are softened to this frequency to keep the integration stable.
+const MAX_ANGULAR_FREQUENCY: f64 = 240.0;
+
+/// The kind of structural member connecting two joints
+#[derive(Debug, Copy, Clone)]
+enum MemberKind {
+    Beam(BeamMaterial),
+    Wire(WireMaterial),
+}
+
+impl MemberKind {
+    /// The level material that members of this kind are built from
+    fn material(&self) -> Material {
+        match *self {
+            MemberKind::Beam(material) => material.into(),
+            MemberKind::Wire(material) => material.into(),
+        }
+    }
+}
+
+/// The highest load a bridge member carried during the simulation
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberStress {
+    /// The vertices the member connects
+    pub edge: Edge,
+    pub material: Material,
+    pub max_load_ratio: f64,
+    pub is_broken: bool,
+}
+
+/// A point mass where members are connected
+#[derive(Debug, Clone)]
+struct Joint {
+    position: Vector,
+    velocity: Vector,
+    mass: f64,
+    is_pinned: bool,
+}
+
+impl Joint {
+    /// Joints without members or anchors don't take part in the simulation
+    fn is_free(&self) -> bool {
+        !self.is_pinned && self.mass > 0.0
+    }
+}
+
+/// A structural member that behaves like a stiff spring between two joints
+#[derive(Debug, Clone)]
+struct Member {
+    /// The order in which the member was added, which stays the same when other members break
+    id: usize,
+    joints: (usize, usize),
+    kind: MemberKind,
+    properties: MaterialProperties,
+    rest_length: f64,
+    spring_constant: f64,
+    damping: f64,
+    /// The axial force, positive when in tension and negative when in compression
+    force: f64,
+    /// Wires that are shorter than their rest length can't push, and hang loose instead
+    is_slack: bool,
+    /// The highest load ratio the member has reached
+    max_load_ratio: f64,
+}
+
+impl Member {
+    /// The ratio between the current force and the strength in the direction it is loaded
+    fn load_ratio(&self) -> f64 {
+        if self.force >= 0.0 {
+            self.force / self.properties.tensile_strength
+        } else {
+            -self.force / self.properties.compressive_strength
+        }
+    }
+}
+
+/// The simulated joint/member model of a bridge
+#[derive(Debug, Clone, Default)]
+pub struct Truss {
+    joints: Vec<Joint>,
+    members: Vec<Member>,
+    /// Members that were overloaded, as they were when they broke
+    broken_members: Vec<Member>,
+    /// External forces on the joints, applied during the next step
+    loads: Vec<Vector>,
+}
+
+impl Truss {
+    pub fn new(level: &Level) -> Result<Self> {
+        let mut truss = Truss {
+            joints: level
+                .vertices
+                .iter()
+                .map(|vertex| Joint {
+                    position: Vector::new(vertex.x, vertex.y),
+                    velocity: Vector::zero(),
+                    mass: 0.0,
+                    is_pinned: false,
+                })
+                .collect(),
+            members: vec![],
+            broken_members: vec![],
+            loads: vec![Vector::zero(); level.vertices.len()],
+        };
+
+        for anchor in &level.anchors {
+            truss
+                .joints
+                .get_mut(anchor.0)
+                .context("Could not find anchor vertex at specified index")?
+                .is_pinned = true;
+        }
+
+        let materials = &level.materials;
+        for (edges, kind, properties) in [
+            (
+                &level.bridge.road,
+                MemberKind::Beam(BeamMaterial::Road),
+                materials.beam(BeamMaterial::Road),
+            ),
+            (
+                &level.bridge.wood,
+                MemberKind::Beam(BeamMaterial::Wood),
+                materials.beam(BeamMaterial::Wood),
+            ),
+            (
+                &level.bridge.steel,
+                MemberKind::Beam(BeamMaterial::Steel),
+                materials.beam(BeamMaterial::Steel),
+            ),
+            (
+                &level.bridge.wire,
+                MemberKind::Wire(WireMaterial::Steel),
+                materials.wire(WireMaterial::Steel),
+            ),
+        ] {
+            truss.add_members(edges, kind, properties)?;
+        }
+
+        truss.calculate_dynamics();
+        Ok(truss)
+    }
+
+    /// Advance the simulation by a single time step, using semi-implicit Euler integration.
+    /// Joints that hit the terrain come to a stop, joints in water are slowed down.
+    /// Returns the scene objects of the members that broke during this step.
+    pub fn step(&mut self, terrain: &Terrain, time_step: f64) -> Vec<Object> {
+        let mut forces: Vec<Vector> = self
+            .joints
+            .iter()
+            .zip(&mut self.loads)
+            .map(|(joint, load)| Vector::new(0.0, -GRAVITY * joint.mass) + std::mem::take(load))
+            .collect();
+
+        for member in &mut self.members {
+            let (index_a, index_b) = member.joints;
+            let (joint_a, joint_b) = (&self.joints[index_a], &self.joints[index_b]);
+
+            let delta = joint_b.position - joint_a.position;
+            let length = delta.length();
+            if length <= f64::EPSILON {
+                continue;
+            }
+            let direction = delta / length;
+
+            let extension = length - member.rest_length;
+            member.is_slack = matches!(member.kind, MemberKind::Wire(_)) && extension < 0.0;
+            if member.is_slack {
+                member.force = 0.0;
+                continue;
+            }
+
+            // Positive values pull the joints together, negative values push them apart
+            member.force = member.spring_constant * extension;
+            member.max_load_ratio = member.max_load_ratio.max(member.load_ratio());
+            if member.load_ratio() > 1.0 {
+                // Broken members no longer transfer any force
+                continue;
+            }
+            let damping_force =
+                member.damping * (joint_b.velocity - joint_a.velocity).dot(direction);
+            let force = direction * (member.force + damping_force);
+
+            forces[index_a] += force;
+            forces[index_b] -= force;
+        }
+
+        let broken_members = self.remove_broken_members(terrain);
+
+        for (joint, force) in self.joints.iter_mut().zip(forces) {
+            if !joint.is_free() {
+                continue;
+            }
+            joint.velocity += force / joint.mass * time_step;
+            terrain.apply_drag(joint.position, &mut joint.velocity, time_step);
+            let position = joint.position + joint.velocity * time_step;
+            if terrain.blocks(joint.position, position) {
+                joint.velocity = Vector::zero();
+            } else {
+                joint.position = position;
+            }
+        }
+
+        broken_members
+    }
+
+    /// Add an external force to a joint, which is applied during the next step
+    pub fn apply_load(&mut self, joint: usize, force: Vector) {
+        if let Some(load) = self.loads.get_mut(joint) {
+            *load += force;
+        }
+    }
+
+    /// The highest load ratio of all members
+    pub fn max_load_ratio(&self) -> f64 {
+        self.members
+            .iter()
+            .map(|member| member.load_ratio())
+            .fold(0.0, f64::max)
+    }
+
+    /// The highest load of every member, including the ones that broke, in the order they were added
+    pub fn member_stresses(&self) -> Vec<MemberStress> {
+        let mut members: Vec<&Member> = self.members.iter().chain(&self.broken_members).collect();
+        members.sort_by_key(|member| member.id);
+        members
+            .into_iter()
+            .map(|member| MemberStress {
+                edge: Edge(VertexIndex(member.joints.0), VertexIndex(member.joints.1)),
+                material: member.kind.material(),
+                max_load_ratio: member.max_load_ratio,
+                is_broken: self
+                    .broken_members
+                    .iter()
+                    .any(|broken| broken.id == member.id),
+            })
+            .collect()
+    }
+
+    /// The road members that vehicles can drive on, at their simulated positions
+    pub fn road_segments(&self) -> Vec<RoadSegment> {
+        self.members
+            .iter()
+            .filter(|member| matches!(member.kind, MemberKind::Beam(BeamMaterial::Road)))
+            .map(|member| RoadSegment {
+                start: self.joints[member.joints.0].position,
+                end: self.joints[member.joints.1].position,
+                joints: Some(member.joints),
+            })
+            .collect()
+    }
+
+    /// Convert the members into scene objects at their simulated positions
+    pub fn scene_objects(&self, terrain: &Terrain) -> Vec<Object> {
+        self.members
+            .iter()
+            .map(|member| self.scene_object(member, terrain))
+            .collect()
+    }
+
+    fn scene_object(&self, member: &Member, terrain: &Terrain) -> Object {
+        let (joint_a, joint_b) = (&self.joints[member.joints.0], &self.joints[member.joints.1]);
+        let line = Line(
+            Self::scene_coordinates(joint_a),
+            Self::scene_coordinates(joint_b),
+        );
+        // A member sinks as soon as one of its ends touches the water
+        let is_sunk =
+            terrain.is_under_water(joint_a.position) || terrain.is_under_water(joint_b.position);
+        match member.kind {
+            MemberKind::Beam(material) => Object::Beam(Beam {
+                material,
+                line,
+                is_static: false,
+                force: member.force,
+                load_ratio: member.load_ratio(),
+                is_sunk,
+            }),
+            MemberKind::Wire(material) => Object::Wire(Wire {
+                material,
+                line,
+                force: member.force,
+                load_ratio: member.load_ratio(),
+                sag: if member.is_slack {
+                    // Parabolic approximation of a cable's sag, based on how much longer it is than its span
+                    let span = (joint_b.position - joint_a.position).length();
+                    (3.0 * span * (member.rest_length - span) / 8.0).sqrt()
+                } else {
+                    0.0
+                },
+                is_sunk,
+            }),
+        }
+    }
+
+    /// Remove all overloaded members from the simulation, along with the mass they added to their joints.
+    /// The remaining members are softened to the lighter joints, to keep the integration stable.
+    fn remove_broken_members(&mut self, terrain: &Terrain) -> Vec<Object> {
+        let (broken, intact): (Vec<Member>, Vec<Member>) = std::mem::take(&mut self.members)
+            .into_iter()
+            .partition(|member| member.load_ratio() > 1.0);
+        self.members = intact;
+        if broken.is_empty() {
+            return vec![];
+        }
+
+        for member in &broken {
+            for index in [member.joints.0, member.joints.1] {
+                let is_connected = self
+                    .members
+                    .iter()
+                    .any(|intact| intact.joints.0 == index || intact.joints.1 == index);
+                self.joints[index].mass = if is_connected {
+                    self.joints[index].mass - member.properties.density * member.rest_length / 2.0
+                } else {
+                    // Joints without members leave the simulation, without a remainder of rounding errors
+                    0.0
+                };
+            }
+        }
+        self.calculate_dynamics();
+        let objects = broken
+            .iter()
+            .map(|member| self.scene_object(member, terrain))
+            .collect();
+        self.broken_members.extend(broken);
+        objects
+    }
+
+    fn add_members(
+        &mut self,
+        edges: &[Edge],
+        kind: MemberKind,
+        properties: &MaterialProperties,
+    ) -> Result<()> {
+        for edge in edges {
+            let (index_a, index_b) = (edge.0 .0, edge.1 .0);
+            ensure!(
+                index_a < self.joints.len() && index_b < self.joints.len(),
+                "Member between vertices {} and {} refers to a vertex that does not exist, the level has {} vertices",
+                index_a,
+                index_b,
+                self.joints.len()
+            );
+
+            let rest_length =
+                (self.joints[index_b].position - self.joints[index_a].position).length();
+            ensure!(
+                rest_length > f64::EPSILON,
+                "Member between vertices {} and {} has zero length",
+                index_a,
+                index_b
+            );
+            ensure!(
+                rest_length <= properties.max_length,
+                "Member between vertices {} and {} is longer than the maximum length of {}",
+                index_a,
+                index_b,
+                properties.max_length
+            );
+
+            // Half of the member's mass is carried by each of its joints
+            for index in [index_a, index_b] {
+                let joint = &mut self.joints[index];
+                if joint.mass == 0.0 {
+                    joint.mass = JOINT_MASS;
+                }
+                joint.mass += properties.density * rest_length / 2.0;
+            }
+
+            self.members.push(Member {
+                id: self.members.len(),
+                joints: (index_a, index_b),
+                kind,
+                properties: *properties,
+                rest_length,
+                spring_constant: 0.0,
+                damping: 0.0,
+                force: 0.0,
+                is_slack: false,
+                max_load_ratio: 0.0,
+            });
+        }
+        Ok(())
+    }
+
+    fn calculate_dynamics(&mut self) {
+        for member in &mut self.members {
+            let (joint_a, joint_b) = (&self.joints[member.joints.0], &self.joints[member.joints.1]);
+            let effective_mass = match (joint_a.is_free(), joint_b.is_free()) {
+                (true, true) => joint_a.mass * joint_b.mass / (joint_a.mass + joint_b.mass),
+                (true, false) => joint_a.mass,
+                (false, true) => joint_b.mass,
+                (false, false) => 0.0,
+            };
+            member.spring_constant = (member.properties.stiffness / member.rest_length)
+                .min(effective_mass * MAX_ANGULAR_FREQUENCY.powi(2));
+            member.damping = 2.0 * DAMPING_RATIO * (member.spring_constant * effective_mass).sqrt();
+        }
+    }
+
+    fn scene_coordinates(joint: &Joint) -> Coordinates {
+        Coordinates {
+            x: joint.position.x,
+            y: joint.position.y,
+        }
+    }
+}
@@ -1,4 +1,951 @@
+use crate::engine::{
+    Engine, FailureReason, Outcome, SimulationEvent, SimulationParameters, SimulationState,
+};
+use crate::level::{
+    Background, Bridge, Color, Coordinates, Edge, Goal, Level, Polyline, Vehicle, VertexIndex,
+    Water,
+};
+use crate::scene::{self, Beam, BeamMaterial, Object, Wire};
+use std::time::Duration;
+
+/// The duration of a typical scene tick in these tests
+const TICK: Duration = Duration::from_millis(250);
+
 #[test]
 fn stub_test() {
     // assert!(false);
 }
+
+/// A wooden deck spanning two anchors, supported from above by a wooden king post truss
+fn king_post_level() -> Level {
+    Level {
+        vertices: vec![
+            Coordinates { x: 0.0, y: 1.0 },
+            Coordinates { x: 2.0, y: 1.0 },
+            Coordinates { x: 1.0, y: 1.0 },
+            Coordinates { x: 1.0, y: 2.0 },
+        ],
+        anchors: vec![VertexIndex(0), VertexIndex(1)],
+        bridge: Bridge {
+            wood: vec![
+                Edge(VertexIndex(0), VertexIndex(3)),
+                Edge(VertexIndex(1), VertexIndex(3)),
+                Edge(VertexIndex(3), VertexIndex(2)),
+            ],
+            road: vec![
+                Edge(VertexIndex(0), VertexIndex(2)),
+                Edge(VertexIndex(2), VertexIndex(1)),
+            ],
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn simulated_beams(engine: &Engine) -> Vec<Beam> {
+    engine
+        .get_scene()
+        .0
+        .iter()
+        .filter_map(|object| match object {
+            Object::Beam(beam) if !beam.is_static => Some(*beam),
+            _ => None,
+        })
+        .collect()
+}
+
+fn simulated_wires(engine: &Engine) -> Vec<Wire> {
+    engine
+        .get_scene()
+        .0
+        .iter()
+        .filter_map(|object| match object {
+            Object::Wire(wire) => Some(*wire),
+            _ => None,
+        })
+        .collect()
+}
+
+fn simulated_vehicles(engine: &Engine) -> Vec<scene::Vehicle> {
+    engine
+        .get_scene()
+        .0
+        .iter()
+        .filter_map(|object| match object {
+            Object::Vehicle(vehicle) => Some(*vehicle),
+            _ => None,
+        })
+        .collect()
+}
+
+fn car(x: f64, y: f64) -> Vehicle {
+    Vehicle {
+        name: "car".to_string(),
+        position: Coordinates { x, y },
+        rotation: 0.0,
+    }
+}
+
+/// A level with a single static road between two vertices, and a car at the specified position
+fn road_level(start: Coordinates, end: Coordinates, car_position: Coordinates) -> Level {
+    Level {
+        vertices: vec![start, end],
+        road: vec![Edge(VertexIndex(0), VertexIndex(1))],
+        vehicles: vec![car(car_position.x, car_position.y)],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn truss_keeps_anchors_pinned_test() {
+    let mut engine = Engine::new();
+    engine.set_level(king_post_level()).unwrap();
+    engine.start_simulation().unwrap();
+    for _ in 0..8 {
+        engine.simulate_scene_tick(TICK).unwrap();
+    }
+
+    // The road members start at the anchors, which should not have moved
+    let beams = simulated_beams(&engine);
+    assert_eq!(beams.len(), 5);
+    assert_eq!(beams[0].line.0.x, 0.0);
+    assert_eq!(beams[0].line.0.y, 1.0);
+    assert_eq!(beams[1].line.1.x, 2.0);
+    assert_eq!(beams[1].line.1.y, 1.0);
+}
+
+#[test]
+fn truss_deforms_under_gravity_test() {
+    let mut engine = Engine::new();
+    engine.set_level(king_post_level()).unwrap();
+    engine.start_simulation().unwrap();
+    for _ in 0..8 {
+        engine.simulate_scene_tick(TICK).unwrap();
+    }
+
+    let beams = simulated_beams(&engine);
+    let center = beams[0].line.1;
+
+    // The center of the deck should sag a little, but the truss should hold it up
+    assert!(center.y < 1.0);
+    assert!(center.y > 0.9);
+
+    // Members that share a vertex should stay connected
+    assert_eq!(beams[1].line.0, center);
+    assert_eq!(beams[4].line.1, center);
+}
+
+#[test]
+fn truss_without_anchors_falls_test() {
+    let mut level = king_post_level();
+    level.anchors.clear();
+
+    let mut engine = Engine::new();
+    engine.set_level(level).unwrap();
+    engine.start_simulation().unwrap();
+    engine.simulate_scene_tick(TICK).unwrap();
+
+    // Without anchors the whole bridge is in free fall
+    let beams = simulated_beams(&engine);
+    assert!(beams[0].line.0.y < 0.8);
+    assert!(beams[2].line.1.y < 1.8);
+}
+
+#[test]
+fn reset_restores_initial_positions_test() {
+    let mut engine = Engine::new();
+    engine.set_level(king_post_level()).unwrap();
+    engine.start_simulation().unwrap();
+    engine.simulate_scene_tick(TICK).unwrap();
+    engine.reset_simulation();
+
+    let beams = simulated_beams(&engine);
+    assert_eq!(beams[0].line.1.y, 1.0);
+}
+
+#[test]
+fn truss_rejects_invalid_members_test() {
+    let mut level = king_post_level();
+    level
+        .bridge
+        .steel
+        .push(Edge(VertexIndex(0), VertexIndex(42)));
+    assert!(Engine::new().set_level(level).is_err());
+
+    let mut level = king_post_level();
+    level
+        .bridge
+        .steel
+        .push(Edge(VertexIndex(2), VertexIndex(2)));
+    assert!(Engine::new().set_level(level).is_err());
+}
+
+#[test]
+fn truss_reports_member_forces_test() {
+    let mut engine = Engine::new();
+    engine.set_level(king_post_level()).unwrap();
+    engine.start_simulation().unwrap();
+    let events = engine.simulate_scene_tick(TICK).unwrap();
+    assert!(events.is_empty());
+
+    // The king post carries the deck, so it should be in tension
+    let beams = simulated_beams(&engine);
+    assert!(beams[4].force > 0.0);
+    assert!(beams[4].load_ratio > 0.0);
+    assert!(beams[4].load_ratio < 1.0);
+
+    // The diagonals carry the king post, so they should be in compression
+    assert!(beams[2].force < 0.0);
+    assert!(beams[3].force < 0.0);
+}
+
+#[test]
+fn overloaded_member_breaks_test() {
+    // A short and weak wooden beam holding up a long steel beam
+    let mut level = Level {
+        vertices: vec![
+            Coordinates { x: 0.0, y: 10.0 },
+            Coordinates { x: 0.0, y: 9.9 },
+            Coordinates { x: 0.0, y: 7.4 },
+            Coordinates { x: 0.0, y: 4.9 },
+        ],
+        anchors: vec![VertexIndex(0)],
+        bridge: Bridge {
+            wood: vec![Edge(VertexIndex(0), VertexIndex(1))],
+            steel: vec![
+                Edge(VertexIndex(1), VertexIndex(2)),
+                Edge(VertexIndex(2), VertexIndex(3)),
+            ],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    level.materials.wood.tensile_strength = 10.0;
+
+    let mut engine = Engine::new();
+    engine.set_level(level).unwrap();
+    engine.start_simulation().unwrap();
+    let events = engine.simulate_scene_tick(TICK).unwrap();
+
+    assert_eq!(events.len(), 1);
+    let SimulationEvent::MemberBroken(object) = &events[0];
+    assert!(matches!(
+        object,
+        Object::Beam(Beam {
+            material: BeamMaterial::Wood,
+            ..
+        })
+    ));
+
+    // Only the steel beams remain, falling down
+    let beams = simulated_beams(&engine);
+    assert_eq!(beams.len(), 2);
+    assert!(matches!(beams[0].material, BeamMaterial::Steel));
+    assert!(beams[0].line.0.y < 9.9);
+}
+
+#[test]
+fn members_stay_stable_after_breaking_test() {
+    // A short wire holding up a heavy steel beam, which is too weak to carry itself
+    let mut level = Level {
+        vertices: vec![
+            Coordinates { x: 0.0, y: 2.0 },
+            Coordinates { x: 0.0, y: 1.9 },
+            Coordinates { x: 0.0, y: -1.5 },
+        ],
+        anchors: vec![VertexIndex(0)],
+        bridge: Bridge {
+            wire: vec![Edge(VertexIndex(0), VertexIndex(1))],
+            steel: vec![Edge(VertexIndex(1), VertexIndex(2))],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    level.materials.steel.tensile_strength = 1.0;
+
+    let mut engine = Engine::new();
+    engine.set_level(level).unwrap();
+    engine.start_simulation().unwrap();
+    let mut events = vec![];
+    for _ in 0..4 {
+        events.append(&mut engine.simulate_scene_tick(TICK).unwrap());
+    }
+
+    // The wire lost most of the mass on its lower end, but doesn't start to oscillate
+    assert_eq!(events.len(), 1);
+    let wires = simulated_wires(&engine);
+    assert!((wires[0].line.1.y - 1.9).abs() < 0.01);
+    assert!(wires[0].load_ratio < 1.0);
+}
+
+#[test]
+fn wires_only_carry_tension_test() {
+    // A wire hanging down from an anchor holds up its lower end
+    let hanging_level = Level {
+        vertices: vec![
+            Coordinates { x: 0.0, y: 2.0 },
+            Coordinates { x: 0.0, y: 1.0 },
+        ],
+        anchors: vec![VertexIndex(0)],
+        bridge: Bridge {
+            wire: vec![Edge(VertexIndex(0), VertexIndex(1))],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut engine = Engine::new();
+    engine.set_level(hanging_level).unwrap();
+    engine.start_simulation().unwrap();
+    engine.simulate_scene_tick(TICK).unwrap();
+
+    let wires = simulated_wires(&engine);
+    assert!(wires[0].force > 0.0);
+    assert_eq!(wires[0].sag, 0.0);
+    assert!(wires[0].line.1.y > 0.99);
+
+    // A wire standing up from an anchor can't push its upper end up, so it falls and goes slack
+    let standing_level = Level {
+        vertices: vec![
+            Coordinates { x: 0.0, y: 1.0 },
+            Coordinates { x: 0.0, y: 2.0 },
+        ],
+        anchors: vec![VertexIndex(0)],
+        bridge: Bridge {
+            wire: vec![Edge(VertexIndex(0), VertexIndex(1))],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut engine = Engine::new();
+    engine.set_level(standing_level).unwrap();
+    engine.start_simulation().unwrap();
+    engine.simulate_scene_tick(TICK).unwrap();
+
+    let wires = simulated_wires(&engine);
+    assert_eq!(wires[0].force, 0.0);
+    assert!(wires[0].sag > 0.0);
+    assert!(wires[0].line.1.y < 1.8);
+}
+
+#[test]
+fn vehicle_drives_along_road_test() {
+    let mut engine = Engine::new();
+    engine
+        .set_level(road_level(
+            Coordinates { x: 0.0, y: 1.0 },
+            Coordinates { x: 3.0, y: 1.0 },
+            Coordinates { x: 0.5, y: 1.0 },
+        ))
+        .unwrap();
+    engine.start_simulation().unwrap();
+    engine.simulate_scene_tick(TICK).unwrap();
+
+    let vehicles = simulated_vehicles(&engine);
+    assert!(vehicles[0].position.x > 0.6);
+    assert!((vehicles[0].position.y - 1.0).abs() < 1e-9);
+    assert!(vehicles[0].rotation.abs() < 1e-9);
+}
+
+#[test]
+fn vehicle_follows_slope_test() {
+    let mut level = road_level(
+        Coordinates { x: 0.0, y: 0.0 },
+        Coordinates { x: 1.0, y: 0.5 },
+        Coordinates { x: 0.5, y: 0.25 },
+    );
+    level.vehicles[0].rotation = 20.0;
+
+    let mut engine = Engine::new();
+    engine.set_level(level).unwrap();
+    engine.start_simulation().unwrap();
+    engine.simulate_scene_tick(TICK).unwrap();
+
+    let vehicles = simulated_vehicles(&engine);
+    let slope = 0.5_f64.atan().to_degrees();
+    assert!((vehicles[0].rotation - slope).abs() < 1e-6);
+    assert!((vehicles[0].position.x / 2.0 - vehicles[0].position.y).abs() < 1e-6);
+}
+
+#[test]
+fn vehicle_falls_off_end_of_road_test() {
+    let mut engine = Engine::new();
+    engine
+        .set_level(road_level(
+            Coordinates { x: 0.0, y: 1.0 },
+            Coordinates { x: 1.0, y: 1.0 },
+            Coordinates { x: 0.8, y: 1.0 },
+        ))
+        .unwrap();
+    engine.start_simulation().unwrap();
+    for _ in 0..4 {
+        engine.simulate_scene_tick(TICK).unwrap();
+    }
+
+    let vehicles = simulated_vehicles(&engine);
+    assert!(vehicles[0].position.x > 1.0);
+    assert!(vehicles[0].position.y < 0.0);
+}
+
+#[test]
+fn vehicle_loads_bridge_test() {
+    let mut level = king_post_level();
+    let mut engine = Engine::new();
+    engine.set_level(king_post_level()).unwrap();
+    engine.start_simulation().unwrap();
+    for _ in 0..8 {
+        engine.simulate_scene_tick(TICK).unwrap();
+    }
+    let unloaded_center = simulated_beams(&engine)[0].line.1;
+
+    // Put a vehicle right above the center of the deck
+    level.vehicles.push(car(1.0, 1.0));
+    engine.set_level(level).unwrap();
+    engine.start_simulation().unwrap();
+    for _ in 0..2 {
+        engine.simulate_scene_tick(TICK).unwrap();
+    }
+    let loaded_center = simulated_beams(&engine)[0].line.1;
+
+    assert!(loaded_center.y < unloaded_center.y);
+
+    // The vehicle should stay on the sagging deck
+    let vehicles = simulated_vehicles(&engine);
+    assert!(vehicles[0].position.y < 1.0);
+    assert!(vehicles[0].position.y > 0.9);
+}
+
+#[test]
+fn vehicle_falls_when_deck_breaks_test() {
+    let mut level = king_post_level();
+    level.bridge.wood.clear();
+    level.materials.road.tensile_strength = 5.0;
+    level.vehicles.push(car(1.0, 1.0));
+
+    let mut engine = Engine::new();
+    engine.set_level(level).unwrap();
+    engine.start_simulation().unwrap();
+    let mut events = vec![];
+    for _ in 0..4 {
+        events.append(&mut engine.simulate_scene_tick(TICK).unwrap());
+    }
+
+    assert!(!events.is_empty());
+    let vehicles = simulated_vehicles(&engine);
+    assert!(vehicles[0].position.y < 0.0);
+}
+
+/// A background with flat ground at the specified height, and optionally water above it
+fn ground_background(ground_level: f64, water_level: Option<f64>) -> Background {
+    let color = Color {
+        red: 0,
+        green: 0,
+        blue: 0,
+    };
+    Background {
+        terrain: vec![Polyline {
+            points: vec![
+                Coordinates {
+                    x: -5.0,
+                    y: ground_level,
+                },
+                Coordinates {
+                    x: 5.0,
+                    y: ground_level,
+                },
+            ],
+            color,
+        }],
+        water: water_level.map(|level| Water { level, color }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn falling_truss_stops_on_terrain_test() {
+    let mut level = king_post_level();
+    level.anchors.clear();
+    level.background = ground_background(0.5, None);
+
+    let mut engine = Engine::new();
+    engine.set_level(level).unwrap();
+    engine.start_simulation().unwrap();
+    for _ in 0..8 {
+        engine.simulate_scene_tick(TICK).unwrap();
+    }
+
+    // The deck falls onto the ground, but not through it
+    let beams = simulated_beams(&engine);
+    assert!(beams[0].line.0.y < 0.6);
+    assert!(beams[0].line.0.y >= 0.5);
+    assert!(beams.iter().all(|beam| !beam.is_sunk));
+}
+
+#[test]
+fn members_in_water_are_sunk_test() {
+    let mut level = king_post_level();
+    level.anchors.clear();
+    level.background = ground_background(-1.0, Some(0.0));
+    level.failure.water_level = Some(0.0);
+
+    let mut engine = Engine::new();
+    engine.set_level(level).unwrap();
+    engine.start_simulation().unwrap();
+    engine.simulate_scene_tick(TICK).unwrap();
+    assert!(simulated_beams(&engine).iter().all(|beam| !beam.is_sunk));
+
+    for _ in 0..8 {
+        engine.simulate_scene_tick(TICK).unwrap();
+    }
+    let beams = simulated_beams(&engine);
+    assert!(beams.iter().any(|beam| beam.is_sunk));
+    assert!(beams[0].line.0.y >= -1.0);
+}
+
+#[test]
+fn vehicle_lands_on_terrain_test() {
+    let mut level = road_level(
+        Coordinates { x: 0.0, y: 1.0 },
+        Coordinates { x: 1.0, y: 1.0 },
+        Coordinates { x: 0.8, y: 1.0 },
+    );
+    level.background = ground_background(0.0, None);
+
+    let mut engine = Engine::new();
+    engine.set_level(level).unwrap();
+    engine.start_simulation().unwrap();
+    for _ in 0..8 {
+        engine.simulate_scene_tick(TICK).unwrap();
+    }
+
+    // The vehicle drops off the road onto the ground, which it can't drive on
+    let vehicles = simulated_vehicles(&engine);
+    assert!(vehicles[0].position.x > 1.0);
+    assert!(vehicles[0].position.x < 1.5);
+    assert!(vehicles[0].position.y >= 0.0);
+    assert!(vehicles[0].position.y < 0.05);
+    assert!(!vehicles[0].is_sunk);
+}
+
+#[test]
+fn vehicle_stops_against_terrain_test() {
+    let mut level = road_level(
+        Coordinates { x: 0.0, y: 1.0 },
+        Coordinates { x: 1.0, y: 1.0 },
+        Coordinates { x: 0.8, y: 1.0 },
+    );
+    // A cliff right after the end of the road
+    level.background.terrain.push(Polyline {
+        points: vec![
+            Coordinates { x: 1.1, y: 0.0 },
+            Coordinates { x: 1.1, y: 2.0 },
+        ],
+        color: Color {
+            red: 0,
+            green: 0,
+            blue: 0,
+        },
+    });
+
+    let mut engine = Engine::new();
+    engine.set_level(level).unwrap();
+    engine.start_simulation().unwrap();
+    for _ in 0..8 {
+        engine.simulate_scene_tick(TICK).unwrap();
+    }
+
+    let vehicles = simulated_vehicles(&engine);
+    assert!(vehicles[0].position.x > 1.0);
+    assert!(vehicles[0].position.x < 1.1);
+}
+
+/// A road that ends at the top of a slope down into a canyon with a flat floor at -0.2
+fn canyon_level() -> Level {
+    let mut level = road_level(
+        Coordinates { x: 0.0, y: 1.0 },
+        Coordinates { x: 1.0, y: 1.0 },
+        Coordinates { x: 0.5, y: 1.0 },
+    );
+    level.background.terrain.push(Polyline {
+        points: vec![
+            Coordinates { x: -1.0, y: 1.0 },
+            Coordinates { x: 1.0, y: 1.0 },
+            Coordinates { x: 1.2, y: 0.3 },
+            Coordinates { x: 1.5, y: -0.2 },
+            Coordinates { x: 3.0, y: -0.2 },
+        ],
+        color: Color {
+            red: 0,
+            green: 0,
+            blue: 0,
+        },
+    });
+    level
+}
+
+#[test]
+fn vehicle_slides_off_cliff_test() {
+    let mut engine = Engine::new();
+    engine.set_level(canyon_level()).unwrap();
+    engine.start_simulation().unwrap();
+    for _ in 0..40 {
+        engine.simulate_scene_tick(TICK).unwrap();
+    }
+
+    // Without a bridge the vehicle slides down the slope onto the canyon floor, instead of hanging on the edge
+    let vehicles = simulated_vehicles(&engine);
+    assert!(vehicles[0].position.x > 1.5);
+    assert!((vehicles[0].position.y + 0.2).abs() < 0.05);
+
+    let mut level = canyon_level();
+    level.goal = Some(Goal::FinishLine(3.0));
+    level.failure.water_level = Some(0.0);
+    engine.set_level(level).unwrap();
+    engine.start_simulation().unwrap();
+    assert_eq!(
+        simulate_until_completed(&mut engine, 40),
+        Some(Outcome::Failure(FailureReason::VehicleDrowned))
+    );
+    assert!(simulated_vehicles(&engine)[0].is_sunk);
+}
+
+#[test]
+fn vehicle_sinking_in_water_fails_level_test() {
+    let mut level = road_level(
+        Coordinates { x: 0.0, y: 1.0 },
+        Coordinates { x: 1.0, y: 1.0 },
+        Coordinates { x: 0.5, y: 1.0 },
+    );
+    level.goal = Some(Goal::FinishLine(3.0));
+    level.background = ground_background(-1.0, Some(0.0));
+    level.failure.water_level = Some(0.0);
+
+    let mut engine = Engine::new();
+    engine.set_level(level).unwrap();
+    engine.start_simulation().unwrap();
+    assert_eq!(
+        simulate_until_completed(&mut engine, 16),
+        Some(Outcome::Failure(FailureReason::VehicleDrowned))
+    );
+    assert!(simulated_vehicles(&engine)[0].is_sunk);
+}
+
+#[test]
+fn empty_bridge_costs_nothing_test() {
+    let mut engine = Engine::new();
+    engine
+        .set_level(road_level(
+            Coordinates { x: 0.0, y: 1.0 },
+            Coordinates { x: 1.0, y: 1.0 },
+            Coordinates { x: 0.5, y: 1.0 },
+        ))
+        .unwrap();
+    let cost = engine.bridge_cost();
+    assert_eq!(cost, 0.0);
+    assert!(cost.is_sign_positive());
+    assert_eq!(format!("{:.0}", cost), "0");
+}
+
+#[test]
+fn simulation_refuses_to_start_over_budget_test() {
+    let mut level = king_post_level();
+    let mut engine = Engine::new();
+    engine.set_level(level.clone()).unwrap();
+    let cost = engine.bridge_cost();
+    assert!(cost > 0.0);
+
+    level.budget = Some(cost - 1.0);
+    engine.set_level(level.clone()).unwrap();
+    assert!(engine.is_over_budget());
+    assert!(engine.start_simulation().is_err());
+    assert!(matches!(
+        engine.simulation_state(),
+        SimulationState::Cleared
+    ));
+
+    level.budget = Some(cost);
+    engine.set_level(level).unwrap();
+    assert!(!engine.is_over_budget());
+    engine.start_simulation().unwrap();
+}
+
+/// Run the simulation until it completes, or give up after a number of ticks
+fn simulate_until_completed(engine: &mut Engine, max_ticks: usize) -> Option<Outcome> {
+    for _ in 0..max_ticks {
+        engine.simulate_scene_tick(TICK).unwrap();
+        if let SimulationState::Completed(result) = engine.simulation_state() {
+            return Some(result.outcome.clone());
+        }
+    }
+    None
+}
+
+#[test]
+fn vehicle_reaching_finish_line_completes_level_test() {
+    let mut level = road_level(
+        Coordinates { x: 0.0, y: 1.0 },
+        Coordinates { x: 3.0, y: 1.0 },
+        Coordinates { x: 0.5, y: 1.0 },
+    );
+    level.goal = Some(Goal::FinishLine(1.0));
+
+    let mut engine = Engine::new();
+    engine.set_level(level).unwrap();
+    engine.start_simulation().unwrap();
+    assert_eq!(
+        simulate_until_completed(&mut engine, 8),
+        Some(Outcome::Success)
+    );
+
+    // The simulation should take as long as it takes the car to drive to the finish
+    let SimulationState::Completed(result) = engine.simulation_state() else {
+        panic!("Simulation should be completed");
+    };
+    assert!((result.statistics.elapsed_time - 1.0).abs() < 0.01);
+    // Static roads cost nothing, and without a budget there is nothing to save on
+    assert_eq!(result.cost, 0.0);
+    assert_eq!(result.score, 1000);
+    assert!(engine.simulate_scene_tick(TICK).is_err());
+}
+
+#[test]
+fn vehicle_reaching_zone_completes_level_test() {
+    let mut level = road_level(
+        Coordinates { x: 0.0, y: 1.0 },
+        Coordinates { x: 1.0, y: 1.0 },
+        Coordinates { x: 0.5, y: 1.0 },
+    );
+    // The goal is below the end of the road, so the car needs to fall into it
+    level.goal = Some(Goal::Zone(
+        Coordinates { x: 1.0, y: -1.0 },
+        Coordinates { x: 2.0, y: 0.0 },
+    ));
+
+    let mut engine = Engine::new();
+    engine.set_level(level).unwrap();
+    engine.start_simulation().unwrap();
+    assert_eq!(
+        simulate_until_completed(&mut engine, 16),
+        Some(Outcome::Success)
+    );
+}
+
+#[test]
+fn vehicle_falling_in_water_fails_level_test() {
+    let mut level = road_level(
+        Coordinates { x: 0.0, y: 1.0 },
+        Coordinates { x: 1.0, y: 1.0 },
+        Coordinates { x: 0.5, y: 1.0 },
+    );
+    level.goal = Some(Goal::FinishLine(3.0));
+    level.failure.water_level = Some(0.0);
+
+    let mut engine = Engine::new();
+    engine.set_level(level).unwrap();
+    engine.start_simulation().unwrap();
+    assert_eq!(
+        simulate_until_completed(&mut engine, 16),
+        Some(Outcome::Failure(FailureReason::VehicleDrowned))
+    );
+}
+
+#[test]
+fn slow_vehicle_fails_level_on_timeout_test() {
+    let mut level = road_level(
+        Coordinates { x: 0.0, y: 1.0 },
+        Coordinates { x: 3.0, y: 1.0 },
+        Coordinates { x: 0.5, y: 1.0 },
+    );
+    level.goal = Some(Goal::FinishLine(2.5));
+    level.failure.timeout = Some(2.0);
+
+    let mut engine = Engine::new();
+    engine.set_level(level).unwrap();
+    engine.start_simulation().unwrap();
+    assert_eq!(
+        simulate_until_completed(&mut engine, 16),
+        Some(Outcome::Failure(FailureReason::Timeout))
+    );
+
+    // Resetting clears the outcome
+    engine.reset_simulation();
+    assert!(matches!(
+        engine.simulation_state(),
+        SimulationState::Cleared
+    ));
+}
+
+#[test]
+fn short_ticks_accumulate_into_physics_steps_test() {
+    let mut engine = Engine::new();
+    engine.set_level(king_post_level()).unwrap();
+    engine.start_simulation().unwrap();
+    engine.simulate_scene_tick(Duration::ZERO).unwrap();
+    let initial_beams = simulated_beams(&engine);
+
+    // A tick shorter than a physics step doesn't advance the simulation
+    engine
+        .simulate_scene_tick(Duration::from_micros(1000))
+        .unwrap();
+    assert_eq!(simulated_beams(&engine), initial_beams);
+
+    // Once the ticks add up to a physics step, it is simulated
+    engine
+        .simulate_scene_tick(Duration::from_micros(1100))
+        .unwrap();
+    assert_ne!(simulated_beams(&engine), initial_beams);
+}
+
+#[test]
+fn simulation_is_independent_of_tick_rate_test() {
+    let mut coarse_engine = Engine::new();
+    coarse_engine.set_level(king_post_level()).unwrap();
+    coarse_engine.start_simulation().unwrap();
+    coarse_engine.simulate_scene_tick(TICK).unwrap();
+
+    let mut fine_engine = Engine::new();
+    fine_engine.set_level(king_post_level()).unwrap();
+    fine_engine.start_simulation().unwrap();
+    for _ in 0..15 {
+        fine_engine
+            .simulate_scene_tick(Duration::from_secs_f64(1.0 / 60.0))
+            .unwrap();
+    }
+
+    assert_eq!(
+        simulated_beams(&fine_engine),
+        simulated_beams(&coarse_engine)
+    );
+}
+
+#[test]
+fn long_ticks_are_capped_test() {
+    let mut engine = Engine::new();
+    engine
+        .set_level(road_level(
+            Coordinates { x: 0.0, y: 1.0 },
+            Coordinates { x: 100.0, y: 1.0 },
+            Coordinates { x: 1.0, y: 1.0 },
+        ))
+        .unwrap();
+    engine.start_simulation().unwrap();
+    engine.simulate_scene_tick(Duration::from_secs(10)).unwrap();
+    let vehicles = simulated_vehicles(&engine);
+    assert!((vehicles[0].position.x - 1.125).abs() < 1e-6);
+}
+
+#[test]
+fn simulation_is_deterministic_test() {
+    let mut first_engine = Engine::new();
+    first_engine.set_level(king_post_level()).unwrap();
+    first_engine.start_simulation().unwrap();
+    let mut second_engine = Engine::new();
+    second_engine.set_level(king_post_level()).unwrap();
+    second_engine.start_simulation().unwrap();
+
+    for _ in 0..8 {
+        first_engine.simulate_scene_tick(TICK).unwrap();
+        second_engine.simulate_scene_tick(TICK).unwrap();
+        assert_eq!(first_engine.get_scene(), second_engine.get_scene());
+    }
+}
+
+#[test]
+fn seek_replays_simulation_test() {
+    let mut engine = Engine::new();
+    engine.set_level(king_post_level()).unwrap();
+    engine.start_simulation().unwrap();
+    engine.simulate_scene_tick(TICK).unwrap();
+    engine.simulate_scene_tick(TICK).unwrap();
+    let scene = engine.get_scene().clone();
+    assert_eq!(engine.step_count(), 240);
+
+    // Seeking backwards and forwards again arrives at the same scene
+    engine.seek(100).unwrap();
+    assert_eq!(engine.step_count(), 100);
+    assert!(matches!(engine.simulation_state(), SimulationState::Paused));
+    assert_ne!(engine.get_scene(), &scene);
+    engine.seek(240).unwrap();
+    assert_eq!(engine.get_scene(), &scene);
+
+    // Stepping one at a time arrives at the same scene as well
+    engine.seek(0).unwrap();
+    for step in 1..=240 {
+        engine.seek(step).unwrap();
+    }
+    assert_eq!(engine.get_scene(), &scene);
+
+    // Resuming continues from the seeked step
+    engine.start_simulation().unwrap();
+    engine.simulate_scene_tick(TICK).unwrap();
+    assert_eq!(engine.step_count(), 360);
+}
+
+#[test]
+fn seek_stops_at_completion_test() {
+    let mut level = road_level(
+        Coordinates { x: 0.0, y: 1.0 },
+        Coordinates { x: 3.0, y: 1.0 },
+        Coordinates { x: 0.5, y: 1.0 },
+    );
+    level.goal = Some(Goal::FinishLine(1.0));
+
+    let mut engine = Engine::new();
+    engine.set_level(level).unwrap();
+    engine.seek(10_000).unwrap();
+    assert!(matches!(
+        engine.simulation_state(),
+        SimulationState::Completed(_)
+    ));
+    assert_eq!(engine.step_count(), 480);
+}
+
+#[test]
+fn step_rate_changes_time_step_test() {
+    let mut engine = Engine::new();
+    engine
+        .set_level(road_level(
+            Coordinates { x: 0.0, y: 1.0 },
+            Coordinates { x: 100.0, y: 1.0 },
+            Coordinates { x: 1.0, y: 1.0 },
+        ))
+        .unwrap();
+    engine
+        .set_simulation_parameters(SimulationParameters { step_rate: 960 })
+        .unwrap();
+    engine.start_simulation().unwrap();
+    engine.simulate_scene_tick(TICK).unwrap();
+    assert_eq!(engine.step_count(), 240);
+    let vehicles = simulated_vehicles(&engine);
+    assert!((vehicles[0].position.x - 1.125).abs() < 1e-6);
+
+    assert!(engine
+        .set_simulation_parameters(SimulationParameters { step_rate: 60 })
+        .is_err());
+}
+
+#[test]
+fn scene_is_sorted_back_to_front_test() {
+    let mut level = king_post_level();
+    level.background = ground_background(-1.0, Some(0.0));
+    // A static road leading onto the bridge
+    level.vertices.push(Coordinates { x: -1.0, y: 1.0 });
+    level.road.push(Edge(VertexIndex(4), VertexIndex(0)));
+    level.vehicles.push(car(1.0, 1.0));
+
+    let mut engine = Engine::new();
+    engine.set_level(level).unwrap();
+    let is_sorted = |engine: &Engine| {
+        let layers: Vec<scene::Layer> = engine.get_scene().0.iter().map(Object::layer).collect();
+        layers.windows(2).all(|pair| pair[0] <= pair[1])
+            && layers.first() == Some(&scene::Layer::Background)
+            && layers.last() == Some(&scene::Layer::Vehicle)
+    };
+    assert!(is_sorted(&engine));
+
+    engine.start_simulation().unwrap();
+    engine.simulate_scene_tick(TICK).unwrap();
+    assert!(is_sorted(&engine));
+}
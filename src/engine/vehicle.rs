@@ -0,0 +1,233 @@
+use super::terrain::Terrain;
+use super::{Vector, GRAVITY};
+use crate::scene::{self, Coordinates, Object};
+use crate::vehicle_types::VehicleType;
+
+/// How far a wheel may be above or below a road to still be in contact with it
+const CONTACT_TOLERANCE: f64 = 0.05;
+
+/// A straight piece of road a vehicle can drive on
+#[derive(Debug, Copy, Clone)]
+pub struct RoadSegment {
+    pub start: Vector,
+    pub end: Vector,
+    /// The simulated joints at the start and end of the segment, static roads have none
+    pub joints: Option<(usize, usize)>,
+}
+
+/// A point where a wheel touches a road segment
+struct Contact {
+    point: Vector,
+    segment: RoadSegment,
+    /// How far along the segment the contact is, from 0.0 at the start to 1.0 at the end
+    fraction: f64,
+}
+
+/// A simulated vehicle that drives along roads, or falls when there is no road under its wheels
+#[derive(Clone)]
+pub struct Vehicle {
+    vehicle_type: &'static VehicleType,
+    /// The point between the wheels where they touch the road
+    position: Vector,
+    /// Rotation in degrees, counter-clockwise
+    rotation: f64,
+    velocity: Vector,
+    is_driving: bool,
+    has_finished: bool,
+    /// Vehicles that fell into the water stop driving, and sink until they reach the ground
+    is_sunk: bool,
+}
+
+impl Vehicle {
+    pub fn new(vehicle: &scene::Vehicle) -> Self {
+        Vehicle {
+            vehicle_type: vehicle.vehicle_type,
+            position: Vector::new(vehicle.position.x, vehicle.position.y),
+            rotation: vehicle.rotation,
+            velocity: Vector::zero(),
+            is_driving: true,
+            has_finished: false,
+            is_sunk: false,
+        }
+    }
+
+    /// The point between the wheels where they touch the road
+    pub fn position(&self) -> Vector {
+        self.position
+    }
+
+    /// Mark the vehicle as having reached the goal
+    pub fn finish(&mut self) {
+        self.has_finished = true;
+    }
+
+    pub fn has_finished(&self) -> bool {
+        self.has_finished
+    }
+
+    pub fn is_sunk(&self) -> bool {
+        self.is_sunk
+    }
+
+    /// Advance the vehicle by a single time step.
+    /// Returns the loads that the wheels put on the joints of the roads they are driving on.
+    pub fn step(
+        &mut self,
+        roads: &[RoadSegment],
+        terrain: &Terrain,
+        time_step: f64,
+    ) -> Vec<(usize, Vector)> {
+        let loads = if self.is_driving {
+            self.drive(roads, time_step)
+        } else {
+            self.fall(roads, terrain, time_step);
+            vec![]
+        };
+
+        if terrain.is_under_water(self.position) {
+            self.is_sunk = true;
+            self.is_driving = false;
+        }
+        loads
+    }
+
+    pub fn scene_object(&self) -> Object {
+        Object::Vehicle(scene::Vehicle {
+            vehicle_type: self.vehicle_type,
+            position: Coordinates {
+                x: self.position.x,
+                y: self.position.y,
+            },
+            rotation: self.rotation,
+            is_sunk: self.is_sunk,
+        })
+    }
+
+    fn heading(&self) -> Vector {
+        let rotation = self.rotation.to_radians();
+        Vector::new(rotation.cos(), rotation.sin())
+    }
+
+    fn drive(&mut self, roads: &[RoadSegment], time_step: f64) -> Vec<(usize, Vector)> {
+        let vehicle_type = self.vehicle_type;
+        let heading = self.heading();
+        let position = self.position + heading * vehicle_type.speed * time_step;
+
+        let contacts = (
+            Self::find_contact(
+                roads,
+                position + heading * vehicle_type.rear_wheel(),
+                CONTACT_TOLERANCE,
+            ),
+            Self::find_contact(
+                roads,
+                position + heading * vehicle_type.front_wheel(),
+                CONTACT_TOLERANCE,
+            ),
+        );
+        let (Some(rear), Some(front)) = contacts else {
+            // One of the wheels lost the road, so the vehicle drives off into the air
+            self.is_driving = false;
+            self.velocity = heading * vehicle_type.speed;
+            self.position = position;
+            return vec![];
+        };
+
+        self.land(&rear, &front);
+        self.velocity = self.heading() * vehicle_type.speed;
+
+        // The weight of the vehicle is divided over the wheels, and then over the joints of each road segment
+        let wheel_load = Vector::new(0.0, -GRAVITY * vehicle_type.mass / 2.0);
+        let mut loads: Vec<(usize, Vector)> = vec![];
+        for contact in [rear, front] {
+            if let Some((joint_start, joint_end)) = contact.segment.joints {
+                loads.push((joint_start, wheel_load * (1.0 - contact.fraction)));
+                loads.push((joint_end, wheel_load * contact.fraction));
+            }
+        }
+        loads
+    }
+
+    fn fall(&mut self, roads: &[RoadSegment], terrain: &Terrain, time_step: f64) {
+        self.velocity.y -= GRAVITY * time_step;
+        terrain.apply_drag(self.position, &mut self.velocity, time_step);
+        let mut position = self.position + self.velocity * time_step;
+        if terrain.collide(self.position, position, &mut self.velocity, time_step) {
+            // The vehicle hit the ground, and slides along it or off its edge
+            position = self.position + self.velocity * time_step;
+            if terrain.blocks(self.position, position) {
+                // The vehicle is stuck between two pieces of ground
+                self.velocity = Vector::zero();
+                return;
+            }
+        }
+        self.position = position;
+
+        if self.velocity.y > 0.0 || self.is_sunk {
+            return;
+        }
+
+        // The vehicle can only land when both its rear and front wheels touch a road
+        let heading = self.heading();
+        let tolerance = CONTACT_TOLERANCE.max(-self.velocity.y * time_step);
+        if let (Some(rear), Some(front)) = (
+            Self::find_contact(
+                roads,
+                self.position + heading * self.vehicle_type.rear_wheel(),
+                tolerance,
+            ),
+            Self::find_contact(
+                roads,
+                self.position + heading * self.vehicle_type.front_wheel(),
+                tolerance,
+            ),
+        ) {
+            self.land(&rear, &front);
+            self.is_driving = true;
+        }
+    }
+
+    /// Put the vehicle's rear and front wheels on the contact points, aligning the vehicle with the slope between them
+    fn land(&mut self, rear: &Contact, front: &Contact) {
+        let delta = front.point - rear.point;
+        let (rear_wheel, front_wheel) = (
+            self.vehicle_type.rear_wheel(),
+            self.vehicle_type.front_wheel(),
+        );
+        // The position is as far between the contacts as it is between the wheels
+        let fraction = if front_wheel > rear_wheel {
+            -rear_wheel / (front_wheel - rear_wheel)
+        } else {
+            0.5
+        };
+        self.position = rear.point + delta * fraction;
+        self.rotation = delta.y.atan2(delta.x).to_degrees();
+    }
+
+    /// Find the highest road surface that is within the tolerance above or below the point
+    fn find_contact(roads: &[RoadSegment], point: Vector, tolerance: f64) -> Option<Contact> {
+        roads
+            .iter()
+            .filter_map(|segment| {
+                let delta = segment.end - segment.start;
+                if delta.x.abs() <= f64::EPSILON {
+                    // Vertical segments have no surface to drive on
+                    return None;
+                }
+                let fraction = (point.x - segment.start.x) / delta.x;
+                if !(0.0..=1.0).contains(&fraction) {
+                    return None;
+                }
+                let surface = segment.start + delta * fraction;
+                if (surface.y - point.y).abs() > tolerance {
+                    return None;
+                }
+                Some(Contact {
+                    point: surface,
+                    segment: *segment,
+                    fraction,
+                })
+            })
+            .max_by(|a, b| a.point.y.total_cmp(&b.point.y))
+    }
+}
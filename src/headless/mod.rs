@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod unittest;
+
+use crate::engine::{
+    Engine, FailureReason, MemberStress, Outcome, SimulationState, SimulationStatistics,
+};
+use crate::level::Level;
+use anyhow::{ensure, Result};
+use std::fmt::Write;
+
+/// The result of simulating a level without a user interface
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// How the simulation completed, or none if it was still running at the end of the duration
+    pub outcome: Option<Outcome>,
+    pub passed: bool,
+    /// Levels without a goal pass when the simulation runs for the whole duration without anything breaking
+    pub has_goal: bool,
+    pub statistics: SimulationStatistics,
+    pub members: Vec<MemberStress>,
+    /// The construction cost of the bridge
+    pub cost: f64,
+    pub budget: Option<f64>,
+    /// A bridge that is over budget is simulated anyway, but the level doesn't pass
+    pub is_over_budget: bool,
+    /// The score of the completed simulation, or none if it didn't complete
+    pub score: Option<u32>,
+}
+
+/// Simulate a level for the specified number of seconds, or until it completes
+pub fn simulate(level: Level, duration: f64) -> Result<Report> {
+    ensure!(
+        duration.is_finite() && duration > 0.0,
+        "Duration should be a positive number of seconds, instead found {}",
+        duration
+    );
+    let has_goal = level.goal.is_some();
+
+    let mut engine = Engine::new();
+    engine.set_level(level)?;
+    let step_rate = engine.simulation_parameters().step_rate as f64;
+    engine.seek((duration * step_rate).round() as u64)?;
+
+    let (outcome, score) = match engine.simulation_state() {
+        SimulationState::Completed(result) => (Some(result.outcome.clone()), Some(result.score)),
+        _ => (None, None),
+    };
+    let is_over_budget = engine.is_over_budget();
+    let broken_member_count = engine.statistics().broken_member_count;
+    Ok(Report {
+        // A level without a goal passes as long as nothing failed or broke
+        passed: !is_over_budget
+            && match &outcome {
+                Some(outcome) => *outcome == Outcome::Success,
+                None => !has_goal && broken_member_count == 0,
+            },
+        outcome,
+        has_goal,
+        statistics: engine.statistics().clone(),
+        members: engine.member_stresses(),
+        cost: engine.bridge_cost(),
+        budget: engine.budget(),
+        is_over_budget,
+        score,
+    })
+}
+
+impl Report {
+    pub fn to_text(&self) -> String {
+        let statistics = &self.statistics;
+        let verdict = if self.passed { "PASS" } else { "FAIL" };
+        let reason = match &self.outcome {
+            Some(Outcome::Success) => "all vehicles reached the goal",
+            Some(Outcome::Failure(FailureReason::VehicleDrowned)) => {
+                "a vehicle fell into the water"
+            }
+            Some(Outcome::Failure(FailureReason::Timeout)) => {
+                "the vehicles did not reach the goal in time"
+            }
+            None if !self.has_goal && statistics.broken_member_count > 0 => "bridge members broke",
+            None if !self.has_goal || self.passed => "nothing failed",
+            None => "the vehicles did not reach the goal",
+        };
+
+        let mut text = format!(
+            "{}: {} after {:.2} s\n",
+            verdict, reason, statistics.elapsed_time
+        );
+        match self.budget {
+            Some(budget) if self.is_over_budget => {
+                let _ = writeln!(text, "Cost: {:.0} of {:.0}, over budget", self.cost, budget);
+            }
+            Some(budget) => {
+                let _ = writeln!(text, "Cost: {:.0} of {:.0}", self.cost, budget);
+            }
+            None => {
+                let _ = writeln!(text, "Cost: {:.0}", self.cost);
+            }
+        }
+        if let Some(score) = self.score {
+            let _ = writeln!(text, "Score: {}", score);
+        }
+        let _ = writeln!(text, "Broken members: {}", statistics.broken_member_count);
+        let _ = writeln!(
+            text,
+            "Highest load: {:.0}%",
+            statistics.max_load_ratio * 100.0
+        );
+        let _ = writeln!(text, "Max stress per member:");
+        for member in &self.members {
+            let _ = writeln!(
+                text,
+                "  {:<5} {:>3} - {:<3} {:>5.1}%{}",
+                member.material.name(),
+                member.edge.0 .0,
+                member.edge.1 .0,
+                member.max_load_ratio * 100.0,
+                if member.is_broken { " broken" } else { "" }
+            );
+        }
+        text
+    }
+
+    pub fn to_json(&self) -> json::JsonValue {
+        let outcome = match &self.outcome {
+            Some(Outcome::Success) => "success",
+            Some(Outcome::Failure(FailureReason::VehicleDrowned)) => "vehicle_drowned",
+            Some(Outcome::Failure(FailureReason::Timeout)) => "timeout",
+            None => "incomplete",
+        };
+        json::object! {
+            passed: self.passed,
+            outcome: outcome,
+            elapsed_time: self.statistics.elapsed_time,
+            broken_member_count: self.statistics.broken_member_count,
+            max_load_ratio: self.statistics.max_load_ratio,
+            cost: self.cost,
+            budget: self.budget,
+            over_budget: self.is_over_budget,
+            score: self.score,
+            members: self
+                .members
+                .iter()
+                .map(|member| {
+                    json::object! {
+                        material: member.material.name(),
+                        vertices: [member.edge.0 .0, member.edge.1 .0],
+                        max_load_ratio: member.max_load_ratio,
+                        broken: member.is_broken,
+                    }
+                })
+                .collect::<Vec<json::JsonValue>>(),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::engine::{FailureReason, Outcome};
+use crate::headless::simulate;
+use crate::level::{Bridge, Coordinates, Edge, Goal, Level, Vehicle, VertexIndex};
+use crate::material::Material;
+
+/// A car driving over a king post bridge between two static roads, towards a finish line
+fn deck_level() -> Level {
+    Level {
+        vertices: vec![
+            Coordinates { x: 0.0, y: 1.0 },
+            Coordinates { x: 1.0, y: 1.0 },
+            Coordinates { x: 2.0, y: 1.0 },
+            Coordinates { x: 3.0, y: 1.0 },
+            Coordinates { x: 1.5, y: 1.0 },
+            Coordinates { x: 1.5, y: 2.0 },
+        ],
+        anchors: vec![VertexIndex(1), VertexIndex(2)],
+        road: vec![
+            Edge(VertexIndex(0), VertexIndex(1)),
+            Edge(VertexIndex(2), VertexIndex(3)),
+        ],
+        bridge: Bridge {
+            road: vec![
+                Edge(VertexIndex(1), VertexIndex(4)),
+                Edge(VertexIndex(4), VertexIndex(2)),
+            ],
+            wood: vec![
+                Edge(VertexIndex(1), VertexIndex(5)),
+                Edge(VertexIndex(2), VertexIndex(5)),
+                Edge(VertexIndex(5), VertexIndex(4)),
+            ],
+            ..Default::default()
+        },
+        vehicles: vec![Vehicle {
+            name: "car".to_string(),
+            position: Coordinates { x: 0.5, y: 1.0 },
+            rotation: 0.0,
+        }],
+        goal: Some(Goal::FinishLine(2.5)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn simulate_passing_level_test() {
+    let report = simulate(deck_level(), 10.0).unwrap();
+    assert!(report.passed);
+    assert_eq!(report.outcome, Some(Outcome::Success));
+    assert!((report.statistics.elapsed_time - 4.0).abs() < 0.01);
+    assert_eq!(report.members.len(), 5);
+    assert_eq!(report.members[0].material, Material::Road);
+    assert_eq!(report.members[4].material, Material::Wood);
+    assert!(report
+        .members
+        .iter()
+        .all(|member| member.max_load_ratio > 0.0 && !member.is_broken));
+
+    let json = report.to_json();
+    assert_eq!(json["passed"], true);
+    assert_eq!(json["outcome"], "success");
+    assert_eq!(json["members"][4]["vertices"], json::array![5, 4]);
+    assert!(report.to_text().starts_with("PASS"));
+}
+
+#[test]
+fn simulate_failing_level_test() {
+    let mut level = deck_level();
+    level.bridge = Bridge::default();
+    level.failure.water_level = Some(0.0);
+
+    let report = simulate(level, 10.0).unwrap();
+    assert!(!report.passed);
+    assert_eq!(
+        report.outcome,
+        Some(Outcome::Failure(FailureReason::VehicleDrowned))
+    );
+    assert_eq!(report.to_json()["outcome"], "vehicle_drowned");
+    assert!(report.to_text().starts_with("FAIL"));
+}
+
+#[test]
+fn simulate_incomplete_level_test() {
+    // The car doesn't reach the goal before the duration ends
+    let report = simulate(deck_level(), 1.0).unwrap();
+    assert!(!report.passed);
+    assert_eq!(report.outcome, None);
+    assert_eq!(report.to_json()["outcome"], "incomplete");
+
+    // Without a goal, a level passes when nothing fails or breaks
+    let mut level = deck_level();
+    level.goal = None;
+    assert!(simulate(level.clone(), 1.0).unwrap().passed);
+    level.materials.wood.tensile_strength = 0.01;
+    let report = simulate(level, 10.0).unwrap();
+    assert_eq!(report.outcome, None);
+    assert!(!report.passed);
+    assert!(report.to_text().starts_with("FAIL: bridge members broke"));
+
+    assert!(simulate(deck_level(), 0.0).is_err());
+}
+
+#[test]
+fn simulate_reports_broken_members_test() {
+    let mut level = deck_level();
+    level.materials.wood.tensile_strength = 0.01;
+
+    // Only the king post is in tension
+    let report = simulate(level, 10.0).unwrap();
+    assert_eq!(report.statistics.broken_member_count, 1);
+    assert!(report.members[4].is_broken);
+    assert!(report.members[4].max_load_ratio > 1.0);
+    assert!(!report.members[0].is_broken);
+    assert!(report.to_text().contains("broken"));
+}
+
+#[test]
+fn simulate_reports_cost_and_score_test() {
+    let mut level = deck_level();
+    let cost = simulate(level.clone(), 10.0).unwrap().cost;
+    assert!(cost > 0.0);
+
+    level.budget = Some(cost + 100.5);
+    let report = simulate(level.clone(), 10.0).unwrap();
+    assert!(report.passed);
+    assert!(!report.is_over_budget);
+    assert_eq!(report.score, Some(1100));
+    assert_eq!(report.to_json()["score"], 1100);
+
+    // A bridge over budget is still simulated, but fails
+    level.budget = Some(cost / 2.0);
+    let report = simulate(level, 10.0).unwrap();
+    assert!(!report.passed);
+    assert!(report.is_over_budget);
+    assert_eq!(report.outcome, Some(Outcome::Success));
+    assert_eq!(report.to_json()["over_budget"], true);
+    assert!(report.to_text().contains("over budget"));
+}
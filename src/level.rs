@@ -1,20 +1,69 @@
+use crate::material::{Material, MaterialTable};
+
 /// Coordinates in 3 dimensions
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Coordinates {
     pub x: f64,
     pub y: f64,
 }
 
+impl Coordinates {
+    /// The shortest distance between this point and the line segment from start to end
+    pub fn distance_to_segment(&self, start: &Coordinates, end: &Coordinates) -> f64 {
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        let square_length = dx * dx + dy * dy;
+        let fraction = if square_length > 0.0 {
+            (((self.x - start.x) * dx + (self.y - start.y) * dy) / square_length).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        (start.x + dx * fraction - self.x).hypot(start.y + dy * fraction - self.y)
+    }
+}
+
+/// A color in red, green and blue components
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+/// A line through a series of points
+#[derive(Debug, PartialEq, Clone)]
+pub struct Polyline {
+    pub points: Vec<Coordinates>,
+    pub color: Color,
+}
+
+/// A body of water, with its surface at the specified height.
+/// It is only drawn, the failure water level decides where objects sink.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Water {
+    pub level: f64,
+    pub color: Color,
+}
+
+/// The scenery behind the bridge, falling vehicles and members collide with its terrain
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Background {
+    pub sky_color: Option<Color>,
+    pub water: Option<Water>,
+    /// The outline of the ground, everything below the lines is solid
+    pub terrain: Vec<Polyline>,
+    pub decorations: Vec<Polyline>,
+}
+
 /// An index of a vertex
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct VertexIndex(pub usize);
 
 /// An edge between two vertices
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Edge(pub VertexIndex, pub VertexIndex);
 
 /// A vehicle that will cross the bridge
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Vehicle {
     pub name: String,
     pub position: Coordinates,
@@ -22,7 +71,7 @@ pub struct Vehicle {
 }
 
 /// A construction build from edges of multiple types
-#[derive(Debug, PartialEq, Eq, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct Bridge {
     pub steel: Vec<Edge>,
     pub wood: Vec<Edge>,
@@ -30,13 +79,104 @@ pub struct Bridge {
     pub road: Vec<Edge>,
 }
 
+impl Bridge {
+    /// The members made of a material
+    pub fn members(&self, material: Material) -> &Vec<Edge> {
+        match material {
+            Material::Road => &self.road,
+            Material::Wood => &self.wood,
+            Material::Steel => &self.steel,
+            Material::Wire => &self.wire,
+        }
+    }
+
+    pub fn members_mut(&mut self, material: Material) -> &mut Vec<Edge> {
+        match material {
+            Material::Road => &mut self.road,
+            Material::Wood => &mut self.wood,
+            Material::Steel => &mut self.steel,
+            Material::Wire => &mut self.wire,
+        }
+    }
+}
+
+/// Where the vehicles need to go to complete the level
+#[derive(Debug, PartialEq, Clone)]
+pub enum Goal {
+    /// Vehicles need to cross a vertical line at the specified x coordinate
+    FinishLine(f64),
+    /// Vehicles need to enter the rectangle between the minimum and maximum corners
+    Zone(Coordinates, Coordinates),
+}
+
+/// Conditions that make the level fail
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct FailureConditions {
+    /// Vehicles that fall below this height have failed to cross the bridge, and everything below it sinks
+    pub water_level: Option<f64>,
+    /// The maximum duration of the simulation in seconds
+    pub timeout: Option<f64>,
+}
+
+/// How hard a level is to complete
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+}
+
+impl std::fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Medium => "Medium",
+            Difficulty::Hard => "Hard",
+            Difficulty::Expert => "Expert",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Information about a level for players, which doesn't affect the simulation
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Metadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub description: Option<String>,
+    pub difficulty: Option<Difficulty>,
+    pub tags: Vec<String>,
+    /// The construction cost the author suggests to stay under
+    pub recommended_budget: Option<f64>,
+}
+
 /// A full level
-#[derive(Debug, PartialEq, Default)]
+#[derive(Debug, PartialEq, Clone, Default)]
 pub struct Level {
-    pub background: String,
+    pub metadata: Metadata,
+    pub background: Background,
     pub vertices: Vec<Coordinates>,
     pub anchors: Vec<VertexIndex>,
     pub road: Vec<Edge>,
     pub bridge: Bridge,
     pub vehicles: Vec<Vehicle>,
+    pub materials: MaterialTable,
+    /// The most the bridge may cost, or none if it may cost anything
+    pub budget: Option<f64>,
+    pub goal: Option<Goal>,
+    pub failure: FailureConditions,
+}
+
+/// A player's attempt at a level, which is stored apart from the level itself
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Solution {
+    /// The path of the level the solution was made for
+    pub level_path: std::path::PathBuf,
+    /// The content hash of the level file the solution was made for
+    pub level_hash: u64,
+    /// The vertices the player added, their indices continue after the level's vertices
+    pub vertices: Vec<Coordinates>,
+    /// The members the player added, which may connect to the level's vertices and the player's vertices
+    pub bridge: Bridge,
 }
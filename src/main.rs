@@ -1,28 +1,111 @@
 use crate::stop_token::StopToken;
 use crate::ui::Gui;
-use anyhow::{Context, Result};
-use clap::Parser;
+use anyhow::{ensure, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
 use signal_hook::{consts::SIGINT, iterator::Signals};
 use std::io;
 use std::ops::Deref;
 use std::panic;
+use std::process::ExitCode;
 use std::sync::Arc;
 use std::thread;
+use std::time::Duration;
 use tracing::error;
 use tracing_subscriber::{EnvFilter, FmtSubscriber};
 
+mod editor;
 mod engine;
+mod headless;
 mod level;
+mod material;
+mod replay;
 mod savefile;
 mod scene;
 mod stop_token;
 mod ui;
+mod validation;
+mod vehicle_types;
 
-/// Search for a pattern in a file and display the lines that contain it.
+/// A bridge building game for the terminal, which can also simulate and validate levels without its interface
 #[derive(Parser)]
+#[command(subcommand_negates_reqs = true, args_conflicts_with_subcommands = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// The path to a level to load
-    level_path: std::path::PathBuf,
+    #[arg(required = true)]
+    level_path: Option<std::path::PathBuf>,
+
+    /// The path to a solution of the level to load
+    #[arg(long)]
+    solution: Option<std::path::PathBuf>,
+
+    /// The path to a replay of the level to play back
+    #[arg(long)]
+    replay: Option<std::path::PathBuf>,
+
+    /// The path to save a replay of every completed simulation to
+    #[arg(long)]
+    record: Option<std::path::PathBuf>,
+
+    /// How often the simulation is advanced, in ticks per second
+    #[arg(long, default_value_t = 60.0, value_parser = parse_rate)]
+    tick_rate: f64,
+
+    /// How often the screen is redrawn at most, in frames per second
+    #[arg(long, default_value_t = 30.0, value_parser = parse_rate)]
+    frame_rate: f64,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Simulate a level without a user interface, exits with a non-zero code if the level fails
+    Simulate {
+        /// The path to a level to simulate
+        level_path: std::path::PathBuf,
+
+        /// The path to a solution of the level to simulate
+        #[arg(long)]
+        solution: Option<std::path::PathBuf>,
+
+        /// The number of seconds to simulate, unless the level completes sooner
+        #[arg(long, default_value_t = 60.0)]
+        duration: f64,
+
+        /// How to print the result
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+
+    /// Check a level for problems, exits with a non-zero code if it contains errors
+    Validate {
+        /// The path to a level to check
+        level_path: std::path::PathBuf,
+
+        /// The path to a solution of the level to check as well
+        #[arg(long)]
+        solution: Option<std::path::PathBuf>,
+
+        /// How to print the problems
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+fn parse_rate(value: &str) -> Result<f64> {
+    let rate: f64 = value.parse().context("Rate should be a number")?;
+    ensure!(
+        rate.is_finite() && rate > 0.0,
+        "Rate should be a positive number"
+    );
+    Ok(rate)
 }
 
 fn install_signal_handler(stop_token: Arc<StopToken>) -> Result<()> {
@@ -72,13 +155,78 @@ fn configure_logger() -> Result<()> {
     Ok(())
 }
 
-fn main() -> Result<()> {
+/// Run a headless simulation and print its report, returns whether the level passed
+fn simulate(
+    level_path: &std::path::PathBuf,
+    solution_path: Option<&std::path::PathBuf>,
+    duration: f64,
+    format: OutputFormat,
+) -> Result<bool> {
+    let (level, _) = savefile::load_with_solution(level_path, solution_path)?;
+    let report = headless::simulate(level, duration)?;
+    match format {
+        OutputFormat::Text => print!("{}", report.to_text()),
+        OutputFormat::Json => println!("{}", report.to_json().pretty(2)),
+    }
+    Ok(report.passed)
+}
+
+/// Validate a level and its solution if any, and print every problem, returns whether they are free of errors
+fn validate(
+    level_path: &std::path::PathBuf,
+    solution_path: Option<&std::path::PathBuf>,
+    format: OutputFormat,
+) -> Result<bool> {
+    let report = validation::load(level_path, solution_path)?;
+    match format {
+        OutputFormat::Text => print!("{}", report.to_text()),
+        OutputFormat::Json => println!("{}", report.to_json().pretty(2)),
+    }
+    Ok(report.is_valid())
+}
+
+fn main() -> Result<ExitCode> {
     let args = Cli::parse();
     configure_logger()?;
 
+    if let Some(command) = &args.command {
+        vehicle_types::use_built_in()?;
+        let passed = match command {
+            Command::Simulate {
+                level_path,
+                solution,
+                duration,
+                format,
+            } => simulate(level_path, solution.as_ref(), *duration, *format)?,
+            Command::Validate {
+                level_path,
+                solution,
+                format,
+            } => validate(level_path, solution.as_ref(), *format)?,
+        };
+        return Ok(if passed {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        });
+    }
+
+    if args.replay.is_some() || args.record.is_some() {
+        // Replays need to be played back with the vehicles they were recorded with
+        vehicle_types::use_built_in()?;
+    }
     let stop_token = Arc::new(StopToken::new());
     install_signal_handler(stop_token.clone())?;
 
-    let mut ui = Gui::new(stop_token, Some(&args.level_path))?;
-    ui.run()
+    let mut ui = Gui::new(
+        stop_token,
+        args.level_path.as_ref(),
+        args.solution.as_ref(),
+        args.replay.as_ref(),
+        args.record.as_ref(),
+        Duration::from_secs_f64(1.0 / args.tick_rate),
+        Duration::from_secs_f64(1.0 / args.frame_rate),
+    )?;
+    ui.run()?;
+    Ok(ExitCode::SUCCESS)
 }
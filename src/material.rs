@@ -0,0 +1,184 @@
+use crate::scene::{BeamMaterial, WireMaterial};
+use anyhow::{ensure, Result};
+
+/// The materials that bridge members can be built from
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Material {
+    Road,
+    Wood,
+    Steel,
+    Wire,
+}
+
+impl Material {
+    /// Every material, in the order they are shown to players
+    pub const ALL: [Material; 4] = [
+        Material::Road,
+        Material::Wood,
+        Material::Steel,
+        Material::Wire,
+    ];
+
+    /// The name of the material, as used in level files
+    pub fn name(&self) -> &'static str {
+        match self {
+            Material::Road => "road",
+            Material::Wood => "wood",
+            Material::Steel => "steel",
+            Material::Wire => "wire",
+        }
+    }
+
+    /// Find a material by the name level files use for it
+    pub fn from_name(name: &str) -> Option<Material> {
+        Material::ALL
+            .into_iter()
+            .find(|material| material.name() == name)
+    }
+}
+
+impl From<BeamMaterial> for Material {
+    fn from(material: BeamMaterial) -> Self {
+        match material {
+            BeamMaterial::Road => Material::Road,
+            BeamMaterial::Wood => Material::Wood,
+            BeamMaterial::Steel => Material::Steel,
+        }
+    }
+}
+
+impl From<WireMaterial> for Material {
+    fn from(material: WireMaterial) -> Self {
+        match material {
+            WireMaterial::Steel => Material::Wire,
+        }
+    }
+}
+
+/// The physical and economic properties of a construction material
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct MaterialProperties {
+    /// Axial stiffness, the force needed per unit of strain
+    pub stiffness: f64,
+    /// Mass per unit length
+    pub density: f64,
+    /// The largest pulling force a member can withstand
+    pub tensile_strength: f64,
+    /// The largest pushing force a member can withstand
+    pub compressive_strength: f64,
+    /// The longest member that can be built
+    pub max_length: f64,
+    /// The construction cost per unit length
+    pub cost_per_length: f64,
+    /// Whether players may build with the material, levels can forbid it to make a puzzle harder
+    pub is_allowed: bool,
+}
+
+/// The properties of every material, levels can override the defaults
+#[derive(Debug, PartialEq, Clone)]
+pub struct MaterialTable {
+    pub wood: MaterialProperties,
+    pub steel: MaterialProperties,
+    pub road: MaterialProperties,
+    pub wire: MaterialProperties,
+}
+
+/// The default members are light compared to the vehicles that cross them, so bridges are mostly loaded by their traffic.
+/// A wooden king post truss carries a car across a gap of one unit at about a fifth of its strength.
+impl Default for MaterialTable {
+    fn default() -> Self {
+        MaterialTable {
+            wood: MaterialProperties {
+                stiffness: 5000.0,
+                density: 0.2,
+                tensile_strength: 60.0,
+                compressive_strength: 45.0,
+                max_length: 2.0,
+                cost_per_length: 100.0,
+                is_allowed: true,
+            },
+            steel: MaterialProperties {
+                stiffness: 15000.0,
+                density: 0.5,
+                tensile_strength: 200.0,
+                compressive_strength: 160.0,
+                max_length: 4.0,
+                cost_per_length: 400.0,
+                is_allowed: true,
+            },
+            road: MaterialProperties {
+                stiffness: 8000.0,
+                density: 0.4,
+                tensile_strength: 80.0,
+                compressive_strength: 80.0,
+                max_length: 2.0,
+                cost_per_length: 200.0,
+                is_allowed: true,
+            },
+            wire: MaterialProperties {
+                stiffness: 10000.0,
+                density: 0.1,
+                tensile_strength: 150.0,
+                compressive_strength: 30.0,
+                max_length: 6.0,
+                cost_per_length: 150.0,
+                is_allowed: true,
+            },
+        }
+    }
+}
+
+impl MaterialProperties {
+    /// Check whether the player may build a member of the specified length from the material
+    pub fn check_buildable(&self, length: f64) -> Result<()> {
+        ensure!(self.is_allowed, "The material is not allowed in this level");
+        ensure!(
+            length <= self.max_length,
+            "A length of {:.2} is longer than the maximum length of {}",
+            length,
+            self.max_length
+        );
+        Ok(())
+    }
+}
+
+impl MaterialTable {
+    pub fn get(&self, material: Material) -> &MaterialProperties {
+        match material {
+            Material::Road => &self.road,
+            Material::Wood => &self.wood,
+            Material::Steel => &self.steel,
+            Material::Wire => &self.wire,
+        }
+    }
+
+    pub fn get_mut(&mut self, material: Material) -> &mut MaterialProperties {
+        match material {
+            Material::Road => &mut self.road,
+            Material::Wood => &mut self.wood,
+            Material::Steel => &mut self.steel,
+            Material::Wire => &mut self.wire,
+        }
+    }
+
+    /// Every material together with its properties
+    pub fn iter(&self) -> impl Iterator<Item = (Material, &MaterialProperties)> {
+        Material::ALL
+            .into_iter()
+            .map(|material| (material, self.get(material)))
+    }
+
+    pub fn beam(&self, material: BeamMaterial) -> &MaterialProperties {
+        match material {
+            BeamMaterial::Wood => &self.wood,
+            BeamMaterial::Steel => &self.steel,
+            BeamMaterial::Road => &self.road,
+        }
+    }
+
+    pub fn wire(&self, material: WireMaterial) -> &MaterialProperties {
+        match material {
+            WireMaterial::Steel => &self.wire,
+        }
+    }
+}
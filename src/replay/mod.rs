@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod unittest;
+
+use crate::engine::SimulationParameters;
+use crate::level::Solution;
+use crate::savefile;
+use anyhow::{ensure, Context, Result};
+use std::fs;
+
+/// A recorded simulation run, which can be played back by simulating the same solution again
+#[derive(Debug, PartialEq, Clone)]
+pub struct Replay {
+    /// The solution that was simulated, which refers to the level the run was recorded on
+    pub solution: Solution,
+    pub parameters: SimulationParameters,
+    /// The number of physics steps that were recorded
+    pub step_count: u64,
+}
+
+pub fn load(path: &std::path::PathBuf) -> Result<Replay> {
+    let json_string = fs::read_to_string(path)
+        .with_context(|| format!("Could not read file `{}`", path.display()))?;
+
+    let json_data = json::parse(&json_string)
+        .with_context(|| format!("Could not parse as json `{}`", path.display()))?;
+
+    parse_replay(&json_data).with_context(|| format!("Could not parse replay `{}`", json_data))
+}
+
+pub fn save(replay: &Replay, path: &std::path::PathBuf) -> Result<()> {
+    fs::write(path, serialize_replay(replay).pretty(2))
+        .with_context(|| format!("Could not write file `{}`", path.display()))
+}
+
+fn parse_replay(root_object: &json::JsonValue) -> Result<Replay> {
+    let parameters = &root_object["parameters"];
+    ensure!(
+        parameters.is_object(),
+        "Expected parameters object, found {}",
+        parameters
+    );
+
+    Ok(Replay {
+        solution: savefile::parse_solution(&root_object["solution"]).context("Invalid solution")?,
+        parameters: SimulationParameters {
+            step_rate: parameters["step_rate"]
+                .as_u32()
+                .context("Step rate is not an unsigned integer")?,
+        },
+        step_count: root_object["step_count"]
+            .as_u64()
+            .context("Step count is not an unsigned integer")?,
+    })
+}
+
+fn serialize_replay(replay: &Replay) -> json::JsonValue {
+    json::object! {
+        solution: savefile::serialize_solution(&replay.solution),
+        parameters: {
+            step_rate: replay.parameters.step_rate,
+        },
+        step_count: replay.step_count,
+    }
+}
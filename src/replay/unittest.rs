@@ -0,0 +1,66 @@
+use crate::engine::SimulationParameters;
+use crate::level::{Bridge, Coordinates, Edge, Solution, VertexIndex};
+use crate::replay::{parse_replay, serialize_replay, Replay};
+use json::object;
+
+fn example_replay() -> Replay {
+    Replay {
+        solution: Solution {
+            level_path: "levels/0.0-Tutorial.json".into(),
+            level_hash: 0x0123456789abcdef,
+            vertices: vec![Coordinates { x: 1.5, y: 2.0 }],
+            bridge: Bridge {
+                wood: vec![
+                    Edge(VertexIndex(2), VertexIndex(5)),
+                    Edge(VertexIndex(3), VertexIndex(5)),
+                ],
+                road: vec![Edge(VertexIndex(2), VertexIndex(3))],
+                ..Default::default()
+            },
+        },
+        parameters: SimulationParameters { step_rate: 960 },
+        step_count: 2200,
+    }
+}
+
+#[test]
+fn parse_replay_test() {
+    // Test valid values
+    assert_eq!(
+        parse_replay(&object! {
+            solution: {
+                level: {path: "levels/0.0-Tutorial.json", hash: "0123456789abcdef"},
+                vertices: [[1.5, 2]],
+                bridge: {members: {steel: [], wood: [[2, 5], [3, 5]], wire: [], road: [[2, 3]]}},
+            },
+            parameters: {step_rate: 960},
+            step_count: 2200,
+        })
+        .unwrap(),
+        example_replay()
+    );
+
+    // Test invalid values
+    assert!(parse_replay(&object! {
+        parameters: {step_rate: 480},
+        step_count: 0,
+    })
+    .is_err());
+    assert!(parse_replay(&object! {
+        solution: {level: {path: "level.json", hash: "00"}, vertices: [], bridge: {}},
+        step_count: 0,
+    })
+    .is_err());
+    assert!(parse_replay(&object! {
+        solution: {level: {path: "level.json", hash: "00"}, vertices: [], bridge: {}},
+        parameters: {step_rate: 480},
+        step_count: -1,
+    })
+    .is_err());
+}
+
+#[test]
+fn serialize_replay_test() {
+    let replay = example_replay();
+    assert_eq!(parse_replay(&serialize_replay(&replay)).unwrap(), replay);
+}
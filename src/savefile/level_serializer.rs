@@ -0,0 +1,201 @@
+use crate::level::{
+    Background, Color, FailureConditions, Goal, Level, Metadata, Vehicle, VertexIndex,
+};
+use crate::material::{MaterialProperties, MaterialTable};
+use crate::savefile::{
+    migration, serialize_bridge, serialize_coordinates, serialize_edges, serialize_vertices,
+};
+
+pub fn serialize_level(level: &Level) -> json::JsonValue {
+    let mut rules = json::JsonValue::new_object();
+    // Optional rules are left out when they have their default values
+    if level.materials != MaterialTable::default() {
+        rules["materials"] = serialize_materials(&level.materials);
+    }
+    if let Some(budget) = level.budget {
+        rules["budget"] = budget.into();
+    }
+    if let Some(goal) = &level.goal {
+        rules["goal"] = serialize_goal(goal);
+    }
+    if level.failure != FailureConditions::default() {
+        rules["failure"] = serialize_failure_conditions(&level.failure);
+    }
+
+    let mut root_object = json::object! {
+        version: migration::CURRENT_VERSION,
+        background: serialize_background(&level.background),
+        rules: rules,
+        foreground: {
+            vertices: serialize_vertices(&level.vertices),
+            anchors: serialize_vertex_indices(&level.anchors),
+            road: serialize_edges(&level.road),
+            bridge: serialize_bridge(&level.bridge),
+            vehicles: serialize_vehicles(&level.vehicles),
+        },
+    };
+    if level.metadata != Metadata::default() {
+        root_object["metadata"] = serialize_metadata(&level.metadata);
+    }
+    root_object
+}
+
+fn serialize_metadata(metadata: &Metadata) -> json::JsonValue {
+    let mut root_object = json::JsonValue::new_object();
+    for (name, value) in [
+        ("title", &metadata.title),
+        ("author", &metadata.author),
+        ("description", &metadata.description),
+    ] {
+        if let Some(value) = value {
+            root_object[name] = value.as_str().into();
+        }
+    }
+    if let Some(difficulty) = metadata.difficulty {
+        root_object["difficulty"] = difficulty.to_string().to_lowercase().into();
+    }
+    if !metadata.tags.is_empty() {
+        root_object["tags"] = metadata.tags.clone().into();
+    }
+    if let Some(budget) = metadata.recommended_budget {
+        root_object["recommended_budget"] = budget.into();
+    }
+    root_object
+}
+
+fn serialize_background(background: &Background) -> json::JsonValue {
+    let mut root_object = json::JsonValue::new_object();
+    if let Some(sky_color) = &background.sky_color {
+        root_object["sky_color"] = serialize_color(sky_color);
+    }
+    if let Some(water) = &background.water {
+        root_object["water"] = json::object! {
+            level: water.level,
+            color: serialize_color(&water.color),
+        };
+    }
+    for (name, polylines) in [
+        ("terrain", &background.terrain),
+        ("decorations", &background.decorations),
+    ] {
+        if !polylines.is_empty() {
+            root_object[name] = polylines
+                .iter()
+                .map(|polyline| {
+                    json::object! {
+                        points: serialize_vertices(&polyline.points),
+                        color: serialize_color(&polyline.color),
+                    }
+                })
+                .collect::<Vec<json::JsonValue>>()
+                .into();
+        }
+    }
+    root_object
+}
+
+fn serialize_color(color: &Color) -> json::JsonValue {
+    format!("#{:02x}{:02x}{:02x}", color.red, color.green, color.blue).into()
+}
+
+fn serialize_materials(materials: &MaterialTable) -> json::JsonValue {
+    let defaults = MaterialTable::default();
+    let mut root_object = json::JsonValue::new_object();
+    for (material, properties) in materials.iter() {
+        let default_properties = defaults.get(material);
+        if properties != default_properties {
+            root_object[material.name()] =
+                serialize_material_properties(properties, default_properties);
+        }
+    }
+    root_object
+}
+
+/// Serialize only the properties that override the defaults
+fn serialize_material_properties(
+    properties: &MaterialProperties,
+    default_properties: &MaterialProperties,
+) -> json::JsonValue {
+    let mut root_object = json::JsonValue::new_object();
+    for (name, property, default_property) in [
+        (
+            "stiffness",
+            properties.stiffness,
+            default_properties.stiffness,
+        ),
+        ("density", properties.density, default_properties.density),
+        (
+            "tensile_strength",
+            properties.tensile_strength,
+            default_properties.tensile_strength,
+        ),
+        (
+            "compressive_strength",
+            properties.compressive_strength,
+            default_properties.compressive_strength,
+        ),
+        (
+            "max_length",
+            properties.max_length,
+            default_properties.max_length,
+        ),
+        (
+            "cost_per_length",
+            properties.cost_per_length,
+            default_properties.cost_per_length,
+        ),
+    ] {
+        if property != default_property {
+            root_object[name] = property.into();
+        }
+    }
+    if properties.is_allowed != default_properties.is_allowed {
+        root_object["allowed"] = properties.is_allowed.into();
+    }
+    root_object
+}
+
+fn serialize_goal(goal: &Goal) -> json::JsonValue {
+    match goal {
+        Goal::FinishLine(x) => json::object! { finish_x: *x },
+        Goal::Zone(min, max) => json::object! {
+            zone: {
+                min: serialize_coordinates(min),
+                max: serialize_coordinates(max),
+            }
+        },
+    }
+}
+
+fn serialize_failure_conditions(failure: &FailureConditions) -> json::JsonValue {
+    let mut root_object = json::JsonValue::new_object();
+    if let Some(water_level) = failure.water_level {
+        root_object["water_level"] = water_level.into();
+    }
+    if let Some(timeout) = failure.timeout {
+        root_object["timeout"] = timeout.into();
+    }
+    root_object
+}
+
+fn serialize_vehicles(vehicles: &[Vehicle]) -> json::JsonValue {
+    vehicles
+        .iter()
+        .map(|vehicle| {
+            json::object! {
+                type: vehicle.name.as_str(),
+                position: serialize_coordinates(&vehicle.position),
+                rotation: vehicle.rotation,
+            }
+        })
+        .collect::<Vec<json::JsonValue>>()
+        .into()
+}
+
+fn serialize_vertex_indices(vertex_indices: &[VertexIndex]) -> json::JsonValue {
+    vertex_indices
+        .iter()
+        .map(|vertex_index| vertex_index.0)
+        .collect::<Vec<usize>>()
+        .into()
+}
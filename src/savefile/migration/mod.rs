@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod unittest;
+
+use anyhow::{anyhow, ensure, Context, Result};
+use json::JsonValue;
+
+/// The level format version that is written, and that older levels are upgraded to.
+/// Version 1 is the format from before the version field was introduced.
+pub const CURRENT_VERSION: u32 = 1;
+
+/// Upgrades a level from the version before it
+type Migration = fn(&mut JsonValue) -> Result<()>;
+
+/// The upgrades from every older version, the first entry will upgrade version 1 to 2
+const MIGRATIONS: [Migration; CURRENT_VERSION as usize - 1] = [];
+
+/// Upgrade a level of any supported version to the current version
+pub fn migrate(root_object: JsonValue) -> Result<JsonValue> {
+    migrate_with(root_object, &MIGRATIONS)
+}
+
+/// Upgrade a level with the specified migrations, the first upgrades version 1 to 2,
+/// and the version after the last one is the newest supported version
+fn migrate_with(mut root_object: JsonValue, migrations: &[Migration]) -> Result<JsonValue> {
+    let newest_version = migrations.len() as u32 + 1;
+    let version = parse_version(&root_object["version"])?;
+    ensure!(
+        version <= newest_version,
+        "Level version {} is newer than the supported version {}, please update the game",
+        version,
+        newest_version
+    );
+
+    for (index, migration) in migrations.iter().enumerate().skip(version as usize - 1) {
+        migration(&mut root_object)
+            .with_context(|| format!("Could not upgrade level to version {}", index + 2))?;
+    }
+    root_object["version"] = newest_version.into();
+    Ok(root_object)
+}
+
+fn parse_version(root_object: &JsonValue) -> Result<u32> {
+    if root_object.is_null() {
+        // Levels from before the version field was introduced
+        return Ok(1);
+    }
+    match root_object.as_u32() {
+        Some(version) if version >= 1 => Ok(version),
+        _ => Err(anyhow!(
+            "Version should be a positive integer, instead found {}",
+            root_object
+        )),
+    }
+}
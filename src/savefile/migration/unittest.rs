@@ -0,0 +1,58 @@
+use crate::savefile::migration::{migrate, migrate_with, Migration, CURRENT_VERSION};
+use anyhow::{anyhow, Result};
+use json::JsonValue;
+
+/// Fake migrations that record the order they ran in, and rename a field like a real format change would
+const FAKE_MIGRATIONS: [Migration; 2] = [
+    |root_object: &mut JsonValue| -> Result<()> {
+        root_object["upgrades"].push("2").unwrap();
+        root_object["road"] = root_object.remove("roads");
+        Ok(())
+    },
+    |root_object: &mut JsonValue| -> Result<()> {
+        root_object["upgrades"].push("3").unwrap();
+        Ok(())
+    },
+];
+
+#[test]
+fn migrate_test() {
+    let level = json::object! {upgrades: [], roads: [[0, 1]]};
+    assert_eq!(migrate(level.clone()).unwrap()["version"], CURRENT_VERSION);
+
+    // Unversioned levels are upgraded from version 1, running every migration in order
+    let migrated = migrate_with(level.clone(), &FAKE_MIGRATIONS).unwrap();
+    assert_eq!(
+        migrated,
+        json::object! {upgrades: ["2", "3"], road: [[0, 1]], version: 3}
+    );
+
+    // Newer levels skip the migrations they don't need
+    let mut version_2 = json::object! {upgrades: [], road: [[0, 1]]};
+    version_2["version"] = 2.into();
+    let migrated = migrate_with(version_2, &FAKE_MIGRATIONS).unwrap();
+    assert_eq!(migrated["upgrades"], json::array!["3"]);
+    assert_eq!(migrated["version"], 3);
+
+    let mut version_3 = level.clone();
+    version_3["version"] = 3.into();
+    let migrated = migrate_with(version_3, &FAKE_MIGRATIONS).unwrap();
+    assert_eq!(migrated["upgrades"], json::array![]);
+
+    let mut version_4 = level;
+    version_4["version"] = 4.into();
+    assert!(migrate_with(version_4, &FAKE_MIGRATIONS).is_err());
+}
+
+#[test]
+fn failed_migration_test() {
+    let failing_migrations: [Migration; 2] =
+        [FAKE_MIGRATIONS[0], |_: &mut JsonValue| -> Result<()> {
+            Err(anyhow!("Roads are missing"))
+        }];
+    let error = migrate_with(json::object! {upgrades: []}, &failing_migrations).unwrap_err();
+    assert_eq!(
+        format!("{:#}", error),
+        "Could not upgrade level to version 3: Roads are missing"
+    );
+}
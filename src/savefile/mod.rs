@@ -1,53 +1,684 @@
 #[cfg(test)]
 mod unittest;
 
-use crate::level::{Bridge, Coordinates, Edge, Level, Vehicle, VertexIndex};
+mod migration;
+
+mod level_serializer;
+
+use crate::level::{
+    Background, Bridge, Color, Coordinates, Difficulty, Edge, FailureConditions, Goal, Level,
+    Metadata, Polyline, Solution, Vehicle, VertexIndex, Water,
+};
+use crate::material::{Material, MaterialProperties, MaterialTable};
 use anyhow::{anyhow, ensure, Context, Result};
 use std::fs;
 
-pub fn load(path: &std::path::PathBuf) -> Result<Level> {
+/// Load a level, together with the hash of its file content
+pub fn load_with_hash(path: &std::path::PathBuf) -> Result<(Level, u64)> {
     let json_string = fs::read_to_string(path)
         .with_context(|| format!("Could not read file `{}`", path.display()))?;
 
     let json_data = json::parse(&json_string)
         .with_context(|| format!("Could not parse as json `{}`", path.display()))?;
 
-    parse_level(&json_data).with_context(|| format!("Could not parse level `{}`", json_data))
+    let level = parse_level(&json_data)
+        .with_context(|| format!("Could not parse level `{}`", json_data))?;
+    Ok((level, content_hash(&json_string)))
+}
+
+/// Load a level and merge the player's solution into it, if any.
+/// Returns the merged level, together with the hash of the level file's content.
+pub fn load_with_solution(
+    level_path: &std::path::PathBuf,
+    solution_path: Option<&std::path::PathBuf>,
+) -> Result<(Level, u64)> {
+    let (level, level_hash) = load_with_hash(level_path)?;
+    let Some(solution_path) = solution_path else {
+        return Ok((level, level_hash));
+    };
+
+    let solution = load_solution(solution_path, level_hash)?;
+    Ok((merge_solution(level, &solution)?, level_hash))
+}
+
+/// Load a solution, which needs to be made for the level with the specified content hash
+pub fn load_solution(path: &std::path::PathBuf, level_hash: u64) -> Result<Solution> {
+    let json_string = fs::read_to_string(path)
+        .with_context(|| format!("Could not read file `{}`", path.display()))?;
+
+    let json_data = json::parse(&json_string)
+        .with_context(|| format!("Could not parse as json `{}`", path.display()))?;
+
+    let solution = parse_solution(&json_data)
+        .map_err(with_location)
+        .with_context(|| format!("Could not parse solution `{}`", json_data))?;
+    ensure!(
+        solution.level_hash == level_hash,
+        "Solution `{}` was made for a different version of level `{}`",
+        path.display(),
+        solution.level_path.display()
+    );
+    Ok(solution)
+}
+
+// Nothing saves levels yet, as the editor saves its changes as a solution
+#[allow(dead_code)]
+pub fn save(level: &Level, path: &std::path::PathBuf) -> Result<()> {
+    fs::write(path, level_serializer::serialize_level(level).pretty(2))
+        .with_context(|| format!("Could not write file `{}`", path.display()))
+}
+
+pub fn save_solution(solution: &Solution, path: &std::path::PathBuf) -> Result<()> {
+    fs::write(path, serialize_solution(solution).pretty(2))
+        .with_context(|| format!("Could not write file `{}`", path.display()))
+}
+
+/// Add the vertices and members of a solution to the level it was made for.
+/// The solution may only use the materials the level allows, up to their maximum length.
+pub fn merge_solution(mut level: Level, solution: &Solution) -> Result<Level> {
+    level.vertices.extend(solution.vertices.iter().cloned());
+    let vertices = &level.vertices;
+    let materials = &level.materials;
+    for (material, properties) in materials.iter() {
+        let members = level.bridge.members_mut(material);
+        for edge in solution.bridge.members(material) {
+            let (Some(start), Some(end)) = (vertices.get(edge.0 .0), vertices.get(edge.1 .0))
+            else {
+                return Err(anyhow!(
+                    "Solution member {:?} refers to a vertex that does not exist",
+                    edge
+                ));
+            };
+            properties
+                .check_buildable((end.x - start.x).hypot(end.y - start.y))
+                .with_context(|| {
+                    format!(
+                        "Solution can't contain {} member between vertices {} and {}",
+                        material.name(),
+                        edge.0 .0,
+                        edge.1 .0
+                    )
+                })?;
+            members.push(edge.clone());
+        }
+    }
+    Ok(level)
+}
+
+/// Take the vertices and members that were added to a level, the inverse of merging a solution into it
+pub fn extract_solution(
+    level: &Level,
+    merged_level: &Level,
+    level_path: std::path::PathBuf,
+    level_hash: u64,
+) -> Result<Solution> {
+    let added = |members: &[Edge], merged_members: &[Edge]| -> Result<Vec<Edge>> {
+        ensure!(
+            merged_members.starts_with(members),
+            "The members of the level were changed"
+        );
+        Ok(merged_members[members.len()..].to_vec())
+    };
+    ensure!(
+        merged_level.vertices.starts_with(&level.vertices),
+        "The vertices of the level were changed"
+    );
+    let mut bridge = Bridge::default();
+    for material in Material::ALL {
+        *bridge.members_mut(material) = added(
+            level.bridge.members(material),
+            merged_level.bridge.members(material),
+        )?;
+    }
+    Ok(Solution {
+        level_path,
+        level_hash,
+        vertices: merged_level.vertices[level.vertices.len()..].to_vec(),
+        bridge,
+    })
+}
+
+/// A hash of a file's content that is stable across platforms and builds, using 64 bit FNV-1a
+pub fn content_hash(content: &str) -> u64 {
+    content.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// A step in the path to a value in a json file, like the `.road` or `[1]` in `$.foreground.road[1]`
+#[derive(Debug)]
+enum PathStep {
+    Key(String),
+    Index(usize),
+}
+
+/// An error that happened while parsing the value at a step in the json file
+#[derive(Debug)]
+struct PathError {
+    step: PathStep,
+    source: anyhow::Error,
+}
+
+impl std::fmt::Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.step {
+            PathStep::Key(key) => write!(f, ".{}", key),
+            PathStep::Index(index) => write!(f, "[{}]", index),
+        }
+    }
+}
+
+impl std::error::Error for PathError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// Adds where a value is in the json file to the errors of parsing it
+trait PathContext<T> {
+    fn at_key(self, key: &str) -> Result<T>;
+    fn at_index(self, index: usize) -> Result<T>;
+}
+
+impl<T> PathContext<T> for Result<T> {
+    fn at_key(self, key: &str) -> Result<T> {
+        self.map_err(|source| {
+            anyhow::Error::new(PathError {
+                step: PathStep::Key(key.to_string()),
+                source,
+            })
+        })
+    }
+
+    fn at_index(self, index: usize) -> Result<T> {
+        self.map_err(|source| {
+            anyhow::Error::new(PathError {
+                step: PathStep::Index(index),
+                source,
+            })
+        })
+    }
+}
+
+/// Split a parse error into the path of the value it was found in, such as `$.foreground.road[1]`, and its message
+pub(crate) fn locate_error(error: &anyhow::Error) -> (String, String) {
+    let mut path = "$".to_string();
+    let mut messages: Vec<String> = vec![];
+    for cause in error.chain() {
+        match cause.downcast_ref::<PathError>() {
+            Some(path_error) => path += &path_error.to_string(),
+            None => messages.push(cause.to_string()),
+        }
+    }
+    (path, messages.join(": "))
+}
+
+/// Turn the path of a parse error into part of its message, for errors that are shown as they are
+fn with_location(error: anyhow::Error) -> anyhow::Error {
+    let (path, message) = locate_error(&error);
+    anyhow!("At {}: {}", path, message)
 }
 
 fn parse_level(root_object: &json::JsonValue) -> Result<Level> {
+    let (level, mut errors) = parse_level_sections(root_object);
+    if errors.is_empty() {
+        Ok(level)
+    } else {
+        Err(with_location(errors.remove(0)))
+    }
+}
+
+/// Parse every section of a level on its own, so that the errors of all sections are found at once.
+/// Sections that could not be parsed are left at their defaults.
+pub(crate) fn parse_level_sections(root_object: &json::JsonValue) -> (Level, Vec<anyhow::Error>) {
+    let mut errors: Vec<anyhow::Error> = vec![];
+    let root_object = match migration::migrate(root_object.clone()).at_key("version") {
+        Ok(root_object) => root_object,
+        Err(error) => {
+            // The rest of the level can't be interpreted without knowing its version
+            errors.push(error);
+            return (Level::default(), errors);
+        }
+    };
+
+    let rules = &root_object["rules"];
+    let mut level = Level {
+        metadata: parse_section(
+            &mut errors,
+            parse_metadata(&root_object["metadata"]).at_key("metadata"),
+        ),
+        background: parse_section(
+            &mut errors,
+            parse_background(&root_object["background"]).at_key("background"),
+        ),
+        materials: parse_section(
+            &mut errors,
+            parse_materials(&rules["materials"])
+                .at_key("materials")
+                .at_key("rules"),
+        ),
+        budget: parse_section(
+            &mut errors,
+            parse_budget(&rules["budget"])
+                .at_key("budget")
+                .at_key("rules"),
+        ),
+        goal: parse_section(
+            &mut errors,
+            parse_goal(&rules["goal"]).at_key("goal").at_key("rules"),
+        ),
+        failure: parse_section(
+            &mut errors,
+            parse_failure_conditions(&rules["failure"])
+                .at_key("failure")
+                .at_key("rules"),
+        ),
+        ..Default::default()
+    };
+
     let foreground = &root_object["foreground"];
+    if !foreground.is_object() {
+        let error = anyhow!(
+            "Foreground should be an object, instead found {}",
+            foreground
+        );
+        errors.extend(Err::<(), _>(error).at_key("foreground").err());
+        return (level, errors);
+    }
+    level.vertices = parse_section(
+        &mut errors,
+        parse_vertices(&foreground["vertices"])
+            .at_key("vertices")
+            .at_key("foreground"),
+    );
+    level.anchors = parse_section(
+        &mut errors,
+        parse_vertex_indices(&foreground["anchors"])
+            .at_key("anchors")
+            .at_key("foreground"),
+    );
+    level.road = parse_section(
+        &mut errors,
+        parse_edges(&foreground["road"])
+            .at_key("road")
+            .at_key("foreground"),
+    );
+    level.bridge = parse_section(
+        &mut errors,
+        parse_bridge(&foreground["bridge"])
+            .at_key("bridge")
+            .at_key("foreground"),
+    );
+    level.vehicles = parse_section(
+        &mut errors,
+        parse_vehicles(&foreground["vehicles"])
+            .at_key("vehicles")
+            .at_key("foreground"),
+    );
+    (level, errors)
+}
+
+/// Pass on the value of a parsed section, or keep its error and use the default value instead
+fn parse_section<T: Default>(errors: &mut Vec<anyhow::Error>, result: Result<T>) -> T {
+    result.unwrap_or_else(|error| {
+        errors.push(error);
+        T::default()
+    })
+}
+
+pub(crate) fn parse_solution(root_object: &json::JsonValue) -> Result<Solution> {
+    let level = &root_object["level"];
+    let level_hash = level["hash"]
+        .as_str()
+        .context("Level hash is not a string")
+        .at_key("hash")
+        .at_key("level")?;
+    Ok(Solution {
+        level_path: level["path"]
+            .as_str()
+            .context("Level path is not a string")
+            .at_key("path")
+            .at_key("level")?
+            .into(),
+        level_hash: u64::from_str_radix(level_hash, 16)
+            .with_context(|| format!("Level hash is not hexadecimal: {}", level_hash))
+            .at_key("hash")
+            .at_key("level")?,
+        vertices: parse_vertices(&root_object["vertices"]).at_key("vertices")?,
+        bridge: parse_bridge(&root_object["bridge"]).at_key("bridge")?,
+    })
+}
+
+pub(crate) fn serialize_solution(solution: &Solution) -> json::JsonValue {
+    json::object! {
+        level: {
+            path: solution.level_path.to_string_lossy().as_ref(),
+            hash: format!("{:016x}", solution.level_hash),
+        },
+        vertices: serialize_vertices(&solution.vertices),
+        bridge: serialize_bridge(&solution.bridge),
+    }
+}
+
+fn parse_metadata(root_object: &json::JsonValue) -> Result<Metadata> {
+    let mut metadata = Metadata::default();
+    if root_object.is_null() {
+        // Levels without metadata are identified by their file name
+        return Ok(metadata);
+    }
+    ensure!(
+        root_object.is_object(),
+        "Metadata should be an object, instead found {}",
+        root_object
+    );
+
+    for (name, value) in root_object.entries() {
+        match name {
+            "title" => metadata.title = Some(parse_string(value).at_key(name)?),
+            "author" => metadata.author = Some(parse_string(value).at_key(name)?),
+            "description" => metadata.description = Some(parse_string(value).at_key(name)?),
+            "difficulty" => metadata.difficulty = Some(parse_difficulty(value).at_key(name)?),
+            "tags" => {
+                ensure!(
+                    value.is_array(),
+                    "Tags should be an array, instead found {}",
+                    value
+                );
+                metadata.tags = value
+                    .members()
+                    .enumerate()
+                    .map(|(index, tag)| parse_string(tag).at_index(index))
+                    .collect::<Result<Vec<String>>>()
+                    .at_key(name)?;
+            }
+            "recommended_budget" => {
+                let budget = value
+                    .as_f64()
+                    .context("Recommended budget is not a number")?;
+                ensure!(
+                    budget >= 0.0,
+                    "Recommended budget should not be negative, instead found {}",
+                    budget
+                );
+                metadata.recommended_budget = Some(budget);
+            }
+            _ => return Err(anyhow!("Not a metadata field: {}", name)),
+        }
+    }
+    Ok(metadata)
+}
+
+fn parse_difficulty(root_object: &json::JsonValue) -> Result<Difficulty> {
+    match parse_string(root_object)?.as_str() {
+        "easy" => Ok(Difficulty::Easy),
+        "medium" => Ok(Difficulty::Medium),
+        "hard" => Ok(Difficulty::Hard),
+        "expert" => Ok(Difficulty::Expert),
+        difficulty => Err(anyhow!("Not a difficulty: {}", difficulty)),
+    }
+}
+
+fn parse_string(root_object: &json::JsonValue) -> Result<String> {
+    Ok(root_object
+        .as_str()
+        .with_context(|| format!("Expected a string, instead found {}", root_object))?
+        .to_string())
+}
+
+fn parse_background(root_object: &json::JsonValue) -> Result<Background> {
+    if root_object.is_null() {
+        return Ok(Background::default());
+    }
+    ensure!(
+        root_object.is_object(),
+        "Background should be an object, instead found {}",
+        root_object
+    );
+
+    let sky_color = &root_object["sky_color"];
+    let water = &root_object["water"];
+    Ok(Background {
+        sky_color: if sky_color.is_null() {
+            None
+        } else {
+            Some(parse_color(sky_color).at_key("sky_color")?)
+        },
+        water: if water.is_null() {
+            None
+        } else {
+            Some(parse_water(water).at_key("water")?)
+        },
+        terrain: parse_polylines(&root_object["terrain"]).at_key("terrain")?,
+        decorations: parse_polylines(&root_object["decorations"]).at_key("decorations")?,
+    })
+}
+
+fn parse_water(root_object: &json::JsonValue) -> Result<Water> {
+    Ok(Water {
+        level: root_object["level"]
+            .as_f64()
+            .context("Water level is not a number")
+            .at_key("level")?,
+        color: parse_color(&root_object["color"]).at_key("color")?,
+    })
+}
+
+fn parse_polylines(root_object: &json::JsonValue) -> Result<Vec<Polyline>> {
+    if root_object.is_null() {
+        return Ok(vec![]);
+    }
     ensure!(
-        foreground.is_object(),
-        "Expected foreground object, found {}",
-        foreground
-    );
-
-    Ok(Level {
-        background: parse_background(&root_object["background"]).context("Invalid background")?,
-        vertices: parse_vertices(&foreground["vertices"]).context("Invalid vertices")?,
-        anchors: parse_vertex_indices(&foreground["anchors"]).context("Invalid anchors")?,
-        road: parse_edges(&foreground["road"]).context("Invalid road")?,
-        bridge: parse_bridge(&foreground["bridge"]).context("Invalid bridge")?,
-        vehicles: parse_vehicles(&foreground["vehicles"]).context("Invalid vehicles")?,
+        root_object.is_array(),
+        "Polylines should be an array, instead found {}",
+        root_object
+    );
+
+    let mut polylines: Vec<Polyline> = vec![];
+    for (index, polyline_json) in root_object.members().enumerate() {
+        polylines.push(parse_polyline(polyline_json).at_index(index)?);
+    }
+    Ok(polylines)
+}
+
+fn parse_polyline(root_object: &json::JsonValue) -> Result<Polyline> {
+    let points = parse_vertices(&root_object["points"]).at_key("points")?;
+    ensure!(
+        points.len() >= 2,
+        "Polylines should contain at least two points, instead found {}",
+        root_object
+    );
+    Ok(Polyline {
+        points,
+        color: parse_color(&root_object["color"]).at_key("color")?,
     })
 }
 
-fn parse_background(_root_object: &json::JsonValue) -> Result<String> {
-    // TODO(Menno 14.12.2022) Need to learn more about the TUI crate to know how we want the background to work
-    Ok(String::from(""))
+/// Parse a color written as a hexadecimal `#rrggbb` string
+pub(crate) fn parse_color(root_object: &json::JsonValue) -> Result<Color> {
+    let text = root_object
+        .as_str()
+        .with_context(|| format!("Color should be a string, instead found {}", root_object))?;
+    let hex = text
+        .strip_prefix('#')
+        .filter(|hex| hex.len() == 6)
+        .with_context(|| {
+            format!(
+                "Color should be formatted as #rrggbb, instead found {}",
+                text
+            )
+        })?;
+    let component = |index: usize| {
+        u8::from_str_radix(&hex[index..index + 2], 16)
+            .with_context(|| format!("Color is not hexadecimal: {}", text))
+    };
+    Ok(Color {
+        red: component(0)?,
+        green: component(2)?,
+        blue: component(4)?,
+    })
 }
 
 fn parse_bridge(root_object: &json::JsonValue) -> Result<Bridge> {
     let members = &root_object["members"];
-    Ok(Bridge {
-        steel: parse_edges(&members["steel"])?,
-        wood: parse_edges(&members["wood"])?,
-        wire: parse_edges(&members["wire"])?,
-        road: parse_edges(&members["road"])?,
+    ensure!(
+        members.is_object() || members.is_null(),
+        "Members should be an object, instead found {}",
+        members
+    );
+    let mut bridge = Bridge::default();
+    for material in Material::ALL {
+        *bridge.members_mut(material) = parse_edges(&members[material.name()])
+            .at_key(material.name())
+            .at_key("members")?;
+    }
+    Ok(bridge)
+}
+
+fn serialize_bridge(bridge: &Bridge) -> json::JsonValue {
+    json::object! {
+        members: {
+            steel: serialize_edges(&bridge.steel),
+            wood: serialize_edges(&bridge.wood),
+            wire: serialize_edges(&bridge.wire),
+            road: serialize_edges(&bridge.road),
+        }
+    }
+}
+
+fn parse_materials(root_object: &json::JsonValue) -> Result<MaterialTable> {
+    let mut materials = MaterialTable::default();
+    if root_object.is_null() {
+        // Levels without overrides use the default materials
+        return Ok(materials);
+    }
+    ensure!(
+        root_object.is_object(),
+        "Materials should be an object, instead found {}",
+        root_object
+    );
+
+    for (name, overrides) in root_object.entries() {
+        let material =
+            Material::from_name(name).with_context(|| format!("Not a material: {}", name))?;
+        let properties = materials.get_mut(material);
+        parse_material_properties(overrides, properties).at_key(name)?;
+    }
+    Ok(materials)
+}
+
+fn parse_material_properties(
+    root_object: &json::JsonValue,
+    properties: &mut MaterialProperties,
+) -> Result<()> {
+    ensure!(
+        root_object.is_object(),
+        "Material properties should be an object, instead found {}",
+        root_object
+    );
+
+    for (name, value) in root_object.entries() {
+        if name == "allowed" {
+            properties.is_allowed = value
+                .as_bool()
+                .context("Material property allowed is not a boolean")
+                .at_key(name)?;
+            continue;
+        }
+        let property = match name {
+            "stiffness" => &mut properties.stiffness,
+            "density" => &mut properties.density,
+            "tensile_strength" => &mut properties.tensile_strength,
+            "compressive_strength" => &mut properties.compressive_strength,
+            "max_length" => &mut properties.max_length,
+            "cost_per_length" => &mut properties.cost_per_length,
+            _ => return Err(anyhow!("Not a material property: {}", name)),
+        };
+        *property = parse_material_property(name, value).at_key(name)?;
+    }
+    Ok(())
+}
+
+fn parse_material_property(name: &str, root_object: &json::JsonValue) -> Result<f64> {
+    let property = root_object
+        .as_f64()
+        .with_context(|| format!("Material property {} is not a number", name))?;
+    // Only the cost may be zero, all physical properties need to be positive
+    ensure!(
+        property > 0.0 || (name == "cost_per_length" && property == 0.0),
+        "Material property {} should be positive, instead found {}",
+        name,
+        property
+    );
+    Ok(property)
+}
+
+fn parse_goal(root_object: &json::JsonValue) -> Result<Option<Goal>> {
+    if root_object.is_null() {
+        // Levels without a goal can only be completed by failing
+        return Ok(None);
+    }
+
+    let finish_x = &root_object["finish_x"];
+    if !finish_x.is_null() {
+        return Ok(Some(Goal::FinishLine(
+            finish_x
+                .as_f64()
+                .context("Finish x is not a number")
+                .at_key("finish_x")?,
+        )));
+    }
+
+    let zone = &root_object["zone"];
+    if !zone.is_null() {
+        let min = parse_coordinates(&zone["min"])
+            .at_key("min")
+            .at_key("zone")?;
+        let max = parse_coordinates(&zone["max"])
+            .at_key("max")
+            .at_key("zone")?;
+        ensure!(
+            min.x <= max.x && min.y <= max.y,
+            "Zone minimum should be below and left of its maximum, instead found {}",
+            zone
+        );
+        return Ok(Some(Goal::Zone(min, max)));
+    }
+
+    Err(anyhow!(
+        "Goal should contain a finish_x or a zone, instead found {}",
+        root_object
+    ))
+}
+
+fn parse_budget(root_object: &json::JsonValue) -> Result<Option<f64>> {
+    let budget = parse_optional_number(root_object)?;
+    if let Some(budget) = budget {
+        ensure!(
+            budget >= 0.0,
+            "Budget should not be negative, instead found {}",
+            budget
+        );
+    }
+    Ok(budget)
+}
+
+fn parse_failure_conditions(root_object: &json::JsonValue) -> Result<FailureConditions> {
+    Ok(FailureConditions {
+        water_level: parse_optional_number(&root_object["water_level"]).at_key("water_level")?,
+        timeout: parse_optional_number(&root_object["timeout"]).at_key("timeout")?,
     })
 }
 
+fn parse_optional_number(root_object: &json::JsonValue) -> Result<Option<f64>> {
+    if root_object.is_null() {
+        Ok(None)
+    } else {
+        Ok(Some(root_object.as_f64().with_context(|| {
+            format!("Expected a number, instead found {}", root_object)
+        })?))
+    }
+}
+
 fn parse_vehicles(root_object: &json::JsonValue) -> Result<Vec<Vehicle>> {
     ensure!(
         root_object.is_array(),
@@ -56,8 +687,8 @@ fn parse_vehicles(root_object: &json::JsonValue) -> Result<Vec<Vehicle>> {
     );
 
     let mut vehicles: Vec<Vehicle> = vec![];
-    for vehicle in root_object.members() {
-        vehicles.push(parse_vehicle(vehicle)?);
+    for (index, vehicle) in root_object.members().enumerate() {
+        vehicles.push(parse_vehicle(vehicle).at_index(index)?);
     }
     Ok(vehicles)
 }
@@ -66,12 +697,14 @@ fn parse_vehicle(root_object: &json::JsonValue) -> Result<Vehicle> {
     Ok(Vehicle {
         name: root_object["type"]
             .as_str()
-            .context("Vehicle type was not string")?
+            .context("Vehicle type was not string")
+            .at_key("type")?
             .to_string(),
-        position: parse_coordinates(&root_object["position"])?,
+        position: parse_coordinates(&root_object["position"]).at_key("position")?,
         rotation: root_object["rotation"]
             .as_f64()
-            .context("Vehicle rotation is not a number")?,
+            .context("Vehicle rotation is not a number")
+            .at_key("rotation")?,
     })
 }
 
@@ -82,40 +715,55 @@ fn parse_vertices(root_object: &json::JsonValue) -> Result<Vec<Coordinates>> {
         root_object
     );
     let mut vertices: Vec<Coordinates> = vec![];
-    for vertex_json in root_object.members() {
-        vertices.push(parse_coordinates(vertex_json)?);
+    for (index, vertex_json) in root_object.members().enumerate() {
+        vertices.push(parse_coordinates(vertex_json).at_index(index)?);
     }
     Ok(vertices)
 }
 
 fn parse_vertex_indices(root_object: &json::JsonValue) -> Result<Vec<VertexIndex>> {
     let mut vertex_indices: Vec<VertexIndex> = vec![];
-    for vertex_index_json in root_object.members() {
-        vertex_indices.push(VertexIndex(
-            vertex_index_json
-                .as_usize()
-                .context("Not an unsigned integer")?,
-        ));
+    for (index, vertex_index_json) in root_object.members().enumerate() {
+        let vertex_index = vertex_index_json
+            .as_usize()
+            .with_context(|| {
+                format!(
+                    "Vertex index should be an unsigned integer, instead found {}",
+                    vertex_index_json
+                )
+            })
+            .at_index(index)?;
+        vertex_indices.push(VertexIndex(vertex_index));
     }
     Ok(vertex_indices)
 }
 
 fn parse_edges(root_object: &json::JsonValue) -> Result<Vec<Edge>> {
     let mut edges: Vec<Edge> = vec![];
-    for edge_json in root_object.members() {
-        let mut vertex_indices = parse_vertex_indices(edge_json)?;
-        ensure!(
-            vertex_indices.len() == 2,
-            "Edges should contain two vertex indices, instead found {}",
-            edge_json
-        );
+    for (index, edge_json) in root_object.members().enumerate() {
+        let mut vertex_indices = parse_vertex_indices(edge_json).at_index(index)?;
+        if vertex_indices.len() != 2 {
+            return Err(anyhow!(
+                "Edges should contain two vertex indices, instead found {}",
+                edge_json
+            ))
+            .at_index(index);
+        }
         // TODO(Menno 03.12.2022) Find cleaner conversion between Vec and tuple
         edges.push(Edge(vertex_indices.remove(0), vertex_indices.remove(0)));
     }
     Ok(edges)
 }
 
-fn parse_coordinates(root_object: &json::JsonValue) -> Result<Coordinates> {
+fn serialize_edges(edges: &[Edge]) -> json::JsonValue {
+    edges
+        .iter()
+        .map(|edge| json::array![edge.0 .0, edge.1 .0])
+        .collect::<Vec<json::JsonValue>>()
+        .into()
+}
+
+pub(crate) fn parse_coordinates(root_object: &json::JsonValue) -> Result<Coordinates> {
     ensure!(
         root_object.len() == 2,
         "Coordinates should contain two numbers, instead found {}",
@@ -131,6 +779,14 @@ fn parse_coordinates(root_object: &json::JsonValue) -> Result<Coordinates> {
     })
 }
 
-pub fn _save(level: &Level) -> Result<()> {
-    Err(anyhow!("Saving is not implemented yet. {:?}", level))
+fn serialize_vertices(vertices: &[Coordinates]) -> json::JsonValue {
+    vertices
+        .iter()
+        .map(serialize_coordinates)
+        .collect::<Vec<json::JsonValue>>()
+        .into()
+}
+
+fn serialize_coordinates(coordinates: &Coordinates) -> json::JsonValue {
+    json::array![coordinates.x, coordinates.y]
 }
@@ -1,7 +1,16 @@
-use crate::level::{Bridge, Coordinates, Edge, Level, Vehicle, VertexIndex};
+use crate::level::{
+    Background, Bridge, Color, Coordinates, Difficulty, Edge, FailureConditions, Goal, Level,
+    Metadata, Polyline, Solution, Vehicle, VertexIndex, Water,
+};
+use crate::material::MaterialTable;
+use crate::savefile::level_serializer::serialize_level;
 use crate::savefile::{
-    parse_bridge, parse_coordinates, parse_edges, parse_level, parse_vehicle, parse_vehicles,
-    parse_vertex_indices, parse_vertices,
+    content_hash, extract_solution, load_with_hash, load_with_solution, locate_error,
+    merge_solution, parse_background, parse_bridge, parse_budget, parse_color, parse_coordinates,
+    parse_edges, parse_failure_conditions, parse_goal, parse_level, parse_level_sections,
+    parse_materials, parse_metadata, parse_solution, parse_vehicle, parse_vehicles,
+    parse_vertex_indices, parse_vertices, save, save_solution, serialize_bridge,
+    serialize_solution,
 };
 use json::{array, JsonValue};
 
@@ -40,7 +49,8 @@ fn parse_level_test() {
         )
         .unwrap(),
         Level {
-            background: "".to_string(),
+            metadata: Metadata::default(),
+            background: Background::default(),
             vertices: vec![
                 Coordinates { x: 0.0, y: 1.0 },
                 Coordinates { x: 3.0, y: 1.0 },
@@ -77,13 +87,530 @@ fn parse_level_test() {
                 position: Coordinates { x: 0.5, y: 1.0 },
                 rotation: 0.0
             }],
+            materials: MaterialTable::default(),
+            budget: None,
+            goal: None,
+            failure: FailureConditions::default(),
+        }
+    );
+}
+
+/// A level that uses every part of the level format
+fn complete_level() -> Level {
+    let mut materials = MaterialTable::default();
+    materials.wood.tensile_strength = 0.1;
+    materials.wire.cost_per_length = 0.0;
+    materials.steel.is_allowed = false;
+    Level {
+        metadata: Metadata {
+            title: Some("Complete".to_string()),
+            author: None,
+            description: Some("Uses \"every\" part of the format".to_string()),
+            difficulty: Some(Difficulty::Expert),
+            tags: vec!["test".to_string(), "wire".to_string()],
+            recommended_budget: Some(1250.0),
+        },
+        background: Background {
+            sky_color: Some(Color {
+                red: 0x87,
+                green: 0xce,
+                blue: 0xeb,
+            }),
+            water: Some(Water {
+                level: -0.5,
+                color: Color {
+                    red: 0,
+                    green: 0,
+                    blue: 255,
+                },
+            }),
+            terrain: vec![Polyline {
+                points: vec![
+                    Coordinates { x: -1.0, y: 1.0 },
+                    Coordinates { x: 1.0, y: 1.0 },
+                ],
+                color: Color {
+                    red: 0x8b,
+                    green: 0x45,
+                    blue: 0x13,
+                },
+            }],
+            decorations: vec![],
+        },
+        vertices: vec![
+            Coordinates { x: 0.0, y: 1.0 },
+            Coordinates { x: 3.0, y: 1.0 },
+            Coordinates { x: 1.0, y: 1.0 },
+            Coordinates { x: 2.0, y: 1.0 },
+            Coordinates {
+                x: 1.0 / 3.0,
+                y: -0.7,
+            },
+            Coordinates { x: 1.5, y: 2e10 },
+        ],
+        anchors: vec![VertexIndex(0), VertexIndex(1)],
+        road: vec![Edge(VertexIndex(0), VertexIndex(2))],
+        bridge: Bridge {
+            steel: vec![Edge(VertexIndex(2), VertexIndex(3))],
+            wood: vec![Edge(VertexIndex(2), VertexIndex(5))],
+            wire: vec![Edge(VertexIndex(3), VertexIndex(5))],
+            road: vec![Edge(VertexIndex(2), VertexIndex(4))],
+        },
+        vehicles: vec![
+            Vehicle {
+                name: "car".to_string(),
+                position: Coordinates { x: 0.5, y: 1.0 },
+                rotation: 0.0,
+            },
+            Vehicle {
+                name: "bus".to_string(),
+                position: Coordinates { x: 0.1, y: 1.2 },
+                rotation: -12.5,
+            },
+        ],
+        materials,
+        budget: Some(1500.0),
+        goal: Some(Goal::Zone(
+            Coordinates { x: 2.5, y: 0.5 },
+            Coordinates { x: 3.0, y: 1.5 },
+        )),
+        failure: FailureConditions {
+            water_level: Some(0.0),
+            timeout: None,
+        },
+    }
+}
+
+#[test]
+fn serialize_level_test() {
+    // Test that levels survive a round trip
+    let level = complete_level();
+    assert_eq!(parse_level(&serialize_level(&level)).unwrap(), level);
+
+    let level = Level {
+        goal: Some(Goal::FinishLine(2.8)),
+        ..Default::default()
+    };
+    assert_eq!(parse_level(&serialize_level(&level)).unwrap(), level);
+
+    // Test that default sections are left out
+    let json = serialize_level(&Level::default());
+    assert_eq!(json["version"], 1);
+    assert!(json["rules"]["materials"].is_null());
+    assert!(json["rules"]["goal"].is_null());
+    assert!(json["rules"]["failure"].is_null());
+    assert!(json["rules"]["budget"].is_null());
+    assert_eq!(
+        serialize_level(&complete_level())["rules"]["materials"],
+        json::object! {
+            wood: {tensile_strength: 0.1},
+            steel: {allowed: false},
+            wire: {cost_per_length: 0.0},
         }
     );
 }
 
+#[test]
+fn save_test() {
+    let path = std::env::temp_dir().join("terminal-bridgebuilder-save-test.json");
+    let level = complete_level();
+    save(&level, &path).unwrap();
+    assert_eq!(load_with_hash(&path).unwrap().0, level);
+    std::fs::remove_file(&path).unwrap();
+
+    // Test the included level
+    let path = std::path::PathBuf::from("levels/0.0-Tutorial.json");
+    let (level, _) = load_with_hash(&path).unwrap();
+    assert_eq!(parse_level(&serialize_level(&level)).unwrap(), level);
+}
+
+fn example_solution() -> Solution {
+    Solution {
+        level_path: "levels/0.0-Tutorial.json".into(),
+        level_hash: 0xfedcba9876543210,
+        vertices: vec![
+            Coordinates { x: 1.5, y: 1.0 },
+            Coordinates { x: 1.5, y: 2.0 },
+        ],
+        bridge: Bridge {
+            wood: vec![
+                Edge(VertexIndex(2), VertexIndex(5)),
+                Edge(VertexIndex(5), VertexIndex(4)),
+            ],
+            road: vec![Edge(VertexIndex(2), VertexIndex(4))],
+            ..Default::default()
+        },
+    }
+}
+
+#[test]
+fn parse_solution_test() {
+    // Test valid values
+    assert_eq!(
+        parse_solution(&json::object! {
+            level: {path: "levels/0.0-Tutorial.json", hash: "fedcba9876543210"},
+            vertices: [[1.5, 1], [1.5, 2]],
+            bridge: {members: {steel: [], wood: [[2, 5], [5, 4]], wire: [], road: [[2, 4]]}},
+        })
+        .unwrap(),
+        example_solution()
+    );
+
+    // Test invalid values
+    assert!(parse_solution(&json::object! {
+        level: {path: "level.json", hash: "not a hash"},
+        vertices: [],
+        bridge: {},
+    })
+    .is_err());
+    assert!(parse_solution(&json::object! {
+        level: {hash: "00"},
+        vertices: [],
+        bridge: {},
+    })
+    .is_err());
+    assert!(parse_solution(&json::object! {
+        level: {path: "level.json", hash: "00"},
+        vertices: [1.5, 1],
+        bridge: {},
+    })
+    .is_err());
+}
+
+#[test]
+fn serialize_solution_test() {
+    let solution = example_solution();
+    assert_eq!(
+        parse_solution(&serialize_solution(&solution)).unwrap(),
+        solution
+    );
+}
+
+#[test]
+fn merge_solution_test() {
+    let level = Level {
+        vertices: vec![
+            Coordinates { x: 0.0, y: 1.0 },
+            Coordinates { x: 3.0, y: 1.0 },
+            Coordinates { x: 1.0, y: 1.0 },
+            Coordinates { x: 2.0, y: 1.0 },
+        ],
+        bridge: Bridge {
+            road: vec![Edge(VertexIndex(0), VertexIndex(2))],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let merged = merge_solution(level.clone(), &example_solution()).unwrap();
+    assert_eq!(merged.vertices.len(), 6);
+    assert_eq!(merged.vertices[5], Coordinates { x: 1.5, y: 2.0 });
+    assert_eq!(
+        merged.bridge.road,
+        vec![
+            Edge(VertexIndex(0), VertexIndex(2)),
+            Edge(VertexIndex(2), VertexIndex(4))
+        ]
+    );
+    assert_eq!(merged.bridge.wood, example_solution().bridge.wood);
+
+    // Members can't refer to vertices beyond the level's and the solution's
+    let mut solution = example_solution();
+    solution.bridge.wire = vec![Edge(VertexIndex(5), VertexIndex(6))];
+    assert!(merge_solution(level.clone(), &solution).is_err());
+
+    // Members can only use allowed materials, up to their maximum length
+    let mut solution = example_solution();
+    solution.bridge.wire = vec![Edge(VertexIndex(0), VertexIndex(1))];
+    assert!(merge_solution(level.clone(), &solution).is_ok());
+    let mut shorter_level = level.clone();
+    shorter_level.materials.wire.max_length = 2.5;
+    assert!(merge_solution(shorter_level, &solution).is_err());
+    let mut wireless_level = level;
+    wireless_level.materials.wire.is_allowed = false;
+    assert!(merge_solution(wireless_level.clone(), &solution).is_err());
+    assert!(merge_solution(wireless_level, &example_solution()).is_ok());
+}
+
+#[test]
+fn extract_solution_test() {
+    let level = Level {
+        vertices: vec![
+            Coordinates { x: 0.0, y: 1.0 },
+            Coordinates { x: 3.0, y: 1.0 },
+            Coordinates { x: 1.0, y: 1.0 },
+            Coordinates { x: 2.0, y: 1.0 },
+        ],
+        bridge: Bridge {
+            road: vec![Edge(VertexIndex(0), VertexIndex(2))],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let solution = example_solution();
+    let merged = merge_solution(level.clone(), &solution).unwrap();
+    assert_eq!(
+        extract_solution(
+            &level,
+            &merged,
+            solution.level_path.clone(),
+            solution.level_hash
+        )
+        .unwrap(),
+        solution
+    );
+
+    // The level's own vertices and members have to be left in place
+    let mut changed = merged.clone();
+    changed.bridge.road.remove(0);
+    assert!(extract_solution(&level, &changed, solution.level_path.clone(), 0).is_err());
+    let mut changed = merged;
+    changed.vertices[0].x = 0.5;
+    assert!(extract_solution(&level, &changed, solution.level_path, 0).is_err());
+}
+
+#[test]
+fn load_with_solution_test() {
+    // Test the included level and solution
+    let level_path = std::path::PathBuf::from("levels/0.0-Tutorial.json");
+    let solution_path = std::path::PathBuf::from("solutions/0.0-Tutorial.json");
+    let (pristine_level, level_hash) = load_with_solution(&level_path, None).unwrap();
+    let (level, solution_level_hash) =
+        load_with_solution(&level_path, Some(&solution_path)).unwrap();
+    assert_eq!(level_hash, solution_level_hash);
+    assert_eq!(level.vertices.len(), pristine_level.vertices.len() + 2);
+    assert_eq!(level.bridge.wood.len(), 3);
+
+    // Test that solutions for other levels are refused
+    let path = std::env::temp_dir().join("terminal-bridgebuilder-solution-test.json");
+    save_solution(&example_solution(), &path).unwrap();
+    assert!(load_with_solution(&level_path, Some(&path)).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn parse_level_sections_test() {
+    let (level, errors) = parse_level_sections(
+        &json::parse(
+            r#"{
+              "metadata": {"tags": ["bridge", 1]},
+              "rules": {"materials": {"wood": {"density": -1}}, "goal": {"finish_x": 2}},
+              "foreground": {
+                "vertices": [[0, 1], [1, 1]],
+                "road": [[0, 1], [1]],
+                "bridge": {"members": {"wire": [[0, "1"]]}},
+                "vehicles": [{"type": "car", "position": [0.5, 1], "rotation": 0}]
+              }
+            }"#,
+        )
+        .unwrap(),
+    );
+    // Every section that could not be parsed has an error at the value it was found in
+    let errors: Vec<(String, String)> = errors.iter().map(locate_error).collect();
+    assert_eq!(
+        errors,
+        vec![
+            (
+                "$.metadata.tags[1]".to_string(),
+                "Expected a string, instead found 1".to_string()
+            ),
+            (
+                "$.rules.materials.wood.density".to_string(),
+                "Material property density should be positive, instead found -1".to_string()
+            ),
+            (
+                "$.foreground.road[1]".to_string(),
+                "Edges should contain two vertex indices, instead found [1]".to_string()
+            ),
+            (
+                "$.foreground.bridge.members.wire[0][1]".to_string(),
+                "Vertex index should be an unsigned integer, instead found 1".to_string()
+            ),
+        ]
+    );
+    // The other sections are still parsed
+    assert_eq!(level.goal, Some(Goal::FinishLine(2.0)));
+    assert_eq!(level.vertices.len(), 2);
+    assert_eq!(level.vehicles.len(), 1);
+    assert_eq!(level.road, vec![]);
+
+    let error =
+        parse_level(&json::parse(r#"{"foreground": {"vertices": [[0]]}}"#).unwrap()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "At $.foreground.vertices[0]: Coordinates should contain two numbers, instead found [0]"
+    );
+}
+
+#[test]
+fn migrate_level_test() {
+    let foreground = json::object! {
+        vertices: [[0, 1], [3, 1]],
+        anchors: [0, 1],
+        road: [[0, 1]],
+        bridge: {},
+        vehicles: [],
+    };
+    let expected_level = Level {
+        vertices: vec![
+            Coordinates { x: 0.0, y: 1.0 },
+            Coordinates { x: 3.0, y: 1.0 },
+        ],
+        anchors: vec![VertexIndex(0), VertexIndex(1)],
+        road: vec![Edge(VertexIndex(0), VertexIndex(1))],
+        goal: Some(Goal::FinishLine(2.8)),
+        failure: FailureConditions {
+            water_level: None,
+            timeout: Some(30.0),
+        },
+        ..Default::default()
+    };
+
+    // Test that levels from before the version field are version 1
+    let unversioned = json::object! {
+        background: {},
+        rules: {goal: {finish_x: 2.8}, failure: {timeout: 30}},
+        foreground: foreground.clone(),
+    };
+    assert_eq!(parse_level(&unversioned).unwrap(), expected_level);
+    let mut version_1 = unversioned.clone();
+    version_1["version"] = 1.into();
+    assert_eq!(parse_level(&version_1).unwrap(), expected_level);
+
+    // Test invalid versions
+    let mut newer_version = version_1.clone();
+    newer_version["version"] = 2.into();
+    let error = parse_level(&newer_version).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("newer than the supported version"));
+    for invalid_version in [json::from(0), json::from(1.5), json::from("1")] {
+        let mut invalid_level = version_1.clone();
+        invalid_level["version"] = invalid_version;
+        assert!(parse_level(&invalid_level).is_err());
+    }
+}
+
+#[test]
+fn parse_metadata_test() {
+    // Test valid values
+    assert_eq!(
+        parse_metadata(&JsonValue::Null).unwrap(),
+        Metadata::default()
+    );
+    assert_eq!(
+        parse_metadata(&json::object! {
+            title: "Tutorial",
+            author: "Menno",
+            description: "Build your first bridge",
+            difficulty: "easy",
+            tags: ["tutorial", "wood"],
+            recommended_budget: 1000,
+        })
+        .unwrap(),
+        Metadata {
+            title: Some("Tutorial".to_string()),
+            author: Some("Menno".to_string()),
+            description: Some("Build your first bridge".to_string()),
+            difficulty: Some(Difficulty::Easy),
+            tags: vec!["tutorial".to_string(), "wood".to_string()],
+            recommended_budget: Some(1000.0),
+        }
+    );
+    assert_eq!(
+        parse_metadata(&json::object! {difficulty: "hard"}).unwrap(),
+        Metadata {
+            difficulty: Some(Difficulty::Hard),
+            ..Default::default()
+        }
+    );
+
+    // Test invalid values
+    assert!(parse_metadata(&json::array!["Tutorial"]).is_err());
+    assert!(parse_metadata(&json::object! {title: 1}).is_err());
+    assert!(parse_metadata(&json::object! {difficulty: "impossible"}).is_err());
+    assert!(parse_metadata(&json::object! {tags: "tutorial"}).is_err());
+    assert!(parse_metadata(&json::object! {tags: [1]}).is_err());
+    assert!(parse_metadata(&json::object! {recommended_budget: -1}).is_err());
+    assert!(parse_metadata(&json::object! {name: "Tutorial"}).is_err());
+}
+
 #[test]
 fn parse_background_test() {
-    // TODO(Menno 08.12.2022) Implement test once background dats structure has been decided
+    // Test valid values
+    assert_eq!(
+        parse_background(&JsonValue::Null).unwrap(),
+        Background::default()
+    );
+    assert_eq!(
+        parse_background(
+            &json::parse(
+                r##"{
+                  "sky_color": "#000000",
+                  "water": {"level": 0.5, "color": "#0000ff"},
+                  "decorations": [{"points": [[0, 2], [1, 2], [1, 3]], "color": "#FFFFFF"}]
+                }"##
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        Background {
+            sky_color: Some(Color {
+                red: 0,
+                green: 0,
+                blue: 0
+            }),
+            water: Some(Water {
+                level: 0.5,
+                color: Color {
+                    red: 0,
+                    green: 0,
+                    blue: 255
+                },
+            }),
+            terrain: vec![],
+            decorations: vec![Polyline {
+                points: vec![
+                    Coordinates { x: 0.0, y: 2.0 },
+                    Coordinates { x: 1.0, y: 2.0 },
+                    Coordinates { x: 1.0, y: 3.0 },
+                ],
+                color: Color {
+                    red: 255,
+                    green: 255,
+                    blue: 255
+                },
+            }],
+        }
+    );
+
+    // Test invalid values
+    assert!(parse_background(&json::parse(r#"[]"#).unwrap()).is_err());
+    assert!(parse_background(&json::parse(r#"{"water": {"level": 0}}"#).unwrap()).is_err());
+    assert!(parse_background(
+        &json::parse(r##"{"terrain": [{"points": [[0, 1]], "color": "#000000"}]}"##).unwrap()
+    )
+    .is_err());
+    assert!(parse_background(&json::parse(r#"{"terrain": {}}"#).unwrap()).is_err());
+}
+
+#[test]
+fn parse_color_test() {
+    // Test valid values
+    assert_eq!(
+        parse_color(&JsonValue::from("#12aB00")).unwrap(),
+        Color {
+            red: 0x12,
+            green: 0xab,
+            blue: 0x00
+        }
+    );
+
+    // Test invalid values
+    assert!(parse_color(&JsonValue::from("12ab00")).is_err());
+    assert!(parse_color(&JsonValue::from("#12ab0")).is_err());
+    assert!(parse_color(&JsonValue::from("#12ab0g")).is_err());
+    assert!(parse_color(&JsonValue::from(12)).is_err());
 }
 
 #[test]
@@ -119,6 +646,121 @@ fn parse_bridge_test() {
     );
 }
 
+#[test]
+fn serialize_bridge_test() {
+    let bridge = Bridge {
+        steel: vec![Edge(VertexIndex(0), VertexIndex(1))],
+        wood: vec![],
+        wire: vec![Edge(VertexIndex(2), VertexIndex(3))],
+        road: vec![
+            Edge(VertexIndex(4), VertexIndex(5)),
+            Edge(VertexIndex(5), VertexIndex(6)),
+        ],
+    };
+    assert_eq!(parse_bridge(&serialize_bridge(&bridge)).unwrap(), bridge);
+}
+
+#[test]
+fn content_hash_test() {
+    // Known FNV-1a values
+    assert_eq!(content_hash(""), 0xcbf29ce484222325);
+    assert_eq!(content_hash("a"), 0xaf63dc4c8601ec8c);
+    assert_ne!(content_hash("{}"), content_hash("{ }"));
+}
+
+#[test]
+fn parse_materials_test() {
+    // Test that missing materials result in the default table
+    assert_eq!(
+        parse_materials(&JsonValue::Null).unwrap(),
+        MaterialTable::default()
+    );
+
+    // Test that unknown materials and properties result in an error
+    assert!(parse_materials(&json::parse(r#"{"gold": {"density": 19.3}}"#).unwrap()).is_err());
+    assert!(parse_materials(&json::parse(r#"{"wood": {"color": 2}}"#).unwrap()).is_err());
+
+    // Test that non-positive properties result in an error
+    assert!(parse_materials(&json::parse(r#"{"wood": {"density": -1}}"#).unwrap()).is_err());
+    assert!(parse_materials(&json::parse(r#"{"wood": {"stiffness": 0}}"#).unwrap()).is_err());
+
+    assert!(parse_materials(&json::parse(r#"{"wood": {"allowed": 0}}"#).unwrap()).is_err());
+
+    // Test valid overrides, only the specified properties should change
+    let mut expected = MaterialTable::default();
+    expected.wood.max_length = 1.5;
+    expected.wire.cost_per_length = 0.0;
+    expected.steel.is_allowed = false;
+    assert_eq!(
+        parse_materials(
+            &json::parse(
+                r#"{"wood": {"max_length": 1.5}, "wire": {"cost_per_length": 0}, "steel": {"allowed": false}}"#
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn parse_goal_test() {
+    // Test that a missing goal is allowed
+    assert_eq!(parse_goal(&JsonValue::Null).unwrap(), None);
+
+    // Test that an empty or inverted goal results in an error
+    assert!(parse_goal(&json::parse("{}").unwrap()).is_err());
+    assert!(
+        parse_goal(&json::parse(r#"{"zone": {"min": [2, 2], "max": [1, 1]}}"#).unwrap()).is_err()
+    );
+
+    // Test valid values
+    assert_eq!(
+        parse_goal(&json::parse(r#"{"finish_x": 2.5}"#).unwrap()).unwrap(),
+        Some(Goal::FinishLine(2.5))
+    );
+    assert_eq!(
+        parse_goal(&json::parse(r#"{"zone": {"min": [1, 0], "max": [2, 1.5]}}"#).unwrap()).unwrap(),
+        Some(Goal::Zone(
+            Coordinates { x: 1.0, y: 0.0 },
+            Coordinates { x: 2.0, y: 1.5 }
+        ))
+    );
+}
+
+#[test]
+fn parse_budget_test() {
+    // Test valid values
+    assert_eq!(parse_budget(&JsonValue::Null).unwrap(), None);
+    assert_eq!(parse_budget(&JsonValue::from(1200)).unwrap(), Some(1200.0));
+
+    // Test invalid values
+    assert!(parse_budget(&JsonValue::from(-1)).is_err());
+    assert!(parse_budget(&JsonValue::from("cheap")).is_err());
+}
+
+#[test]
+fn parse_failure_conditions_test() {
+    // Test that missing conditions are allowed
+    assert_eq!(
+        parse_failure_conditions(&JsonValue::Null).unwrap(),
+        FailureConditions::default()
+    );
+
+    // Test that a non-number value results in an error
+    assert!(parse_failure_conditions(&json::parse(r#"{"timeout": "soon"}"#).unwrap()).is_err());
+
+    // Test valid values
+    assert_eq!(
+        parse_failure_conditions(&json::parse(r#"{"water_level": -0.5, "timeout": 30}"#).unwrap())
+            .unwrap(),
+        FailureConditions {
+            water_level: Some(-0.5),
+            timeout: Some(30.0)
+        }
+    );
+}
+
 #[test]
 fn parse_vehicles_test() {
     // Test valid values
@@ -159,6 +801,7 @@ fn parse_vehicle_test() {
 }
 
 #[test]
+#[allow(clippy::approx_constant)]
 fn parse_vertices_test() {
     let mut coordinates = array![];
 
@@ -216,6 +859,7 @@ fn parse_edges_test() {
 }
 
 #[test]
+#[allow(clippy::approx_constant)]
 fn parse_coordinates_test() {
     // Test that null values result in an error
     assert!(parse_coordinates(&JsonValue::Null).is_err());
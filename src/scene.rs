@@ -1,5 +1,6 @@
 use crate::level;
-use anyhow::{anyhow, Context, Result};
+use crate::vehicle_types::{self, VehicleType};
+use anyhow::{Context, Result};
 
 /// Scene coordinates
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -18,17 +19,20 @@ impl Coordinates {
 }
 
 /// A pair of Scene coordinates, creating a line between the pair
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub struct Line(pub(crate) Coordinates, pub(crate) Coordinates);
 
-#[derive(Debug, Copy, Clone)]
+/// A piece of scenery behind the bridge
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub struct Background {
     pub line: Line,
-    pub color: u16,
+    pub color: level::Color,
+    /// Whether the area below the line is filled, like solid ground or water
+    pub is_filled: bool,
 }
 
 /// The material a beam is made of
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub enum BeamMaterial {
     Wood,
     Steel,
@@ -36,63 +40,90 @@ pub enum BeamMaterial {
 }
 
 /// A rigid structural member
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub struct Beam {
     pub material: BeamMaterial,
     pub line: Line,
     pub is_static: bool,
+    /// The axial force, positive when in tension and negative when in compression
+    pub force: f64,
+    /// The ratio between the force and the material's strength, the beam breaks above 1.0
+    pub load_ratio: f64,
+    /// Whether the beam has fallen into the water
+    pub is_sunk: bool,
 }
 
 /// The material a Wire is made of
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub enum WireMaterial {
     Steel,
 }
 
 /// A flexible structural member that can only maintain tension
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub struct Wire {
     pub material: WireMaterial,
     pub line: Line,
-}
-
-/// The type of vehicle
-#[derive(Debug, Copy, Clone)]
-pub enum VehicleType {
-    Bus,
-    Car,
+    /// The axial force, positive when in tension and negative when in compression
+    pub force: f64,
+    /// The ratio between the force and the material's strength, the wire breaks above 1.0
+    pub load_ratio: f64,
+    /// How far the middle of a slack wire hangs below its straight line, zero when taut
+    pub sag: f64,
+    /// Whether the wire has fallen into the water
+    pub is_sunk: bool,
 }
 
 /// A vehicle that can self propel along a road
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub struct Vehicle {
-    pub vehicle_type: VehicleType,
+    pub vehicle_type: &'static VehicleType,
     pub position: Coordinates,
     pub rotation: f64,
+    /// Whether the vehicle has fallen into the water
+    pub is_sunk: bool,
 }
 
 /// Generalization of all scene objects
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub enum Object {
+    Background(Background),
     Wire(Wire),
     Beam(Beam),
     Vehicle(Vehicle),
 }
 
-// TODO(Menno 28.12.2022) Implement ordering for objects
-// impl Object {
-//     fn cmp (&self, other: &Self){
-//         if std::mem::discriminant(self) == std::mem::discriminant( other) {
-//
-//         }
-//         match other {
-//
-//         }
-//     }
-// }
+/// The layers that objects are drawn in, from back to front.
+/// Overlays, like the editor's cursor, are drawn in front of all layers.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub enum Layer {
+    Background,
+    StaticRoad,
+    Road,
+    Wood,
+    Steel,
+    Wire,
+    Vehicle,
+}
+
+impl Object {
+    pub fn layer(&self) -> Layer {
+        match self {
+            Object::Background(_) => Layer::Background,
+            Object::Beam(beam) if beam.is_static => Layer::StaticRoad,
+            Object::Beam(beam) => match beam.material {
+                BeamMaterial::Road => Layer::Road,
+                BeamMaterial::Wood => Layer::Wood,
+                BeamMaterial::Steel => Layer::Steel,
+            },
+            Object::Wire(_) => Layer::Wire,
+            Object::Vehicle(_) => Layer::Vehicle,
+        }
+    }
+}
 
 /// A level transformed into renderable objects
-#[derive(Default, Clone)]
+#[derive(Debug, Default, PartialEq, Clone)]
 pub struct Scene(pub Vec<Object>);
 
 // TODO(Menno 28.12.2022) implement iterator for Scene references to make the Vec private
@@ -108,6 +139,8 @@ impl IntoIterator for Scene {
 impl Scene {
     pub fn new(level: &level::Level) -> Result<Self> {
         let mut scene: Self = Default::default();
+        scene.0.append(&mut Self::convert_background(level));
+
         // Add static roads
         scene.0.append(&mut Self::convert_beams(
             &level.vertices,
@@ -146,37 +179,82 @@ impl Scene {
             WireMaterial::Steel,
         )?);
 
-        // TODO(Menno 26.12.2022) Convert remaining level objects into scene objects, i.e. vehicles and the background
+        scene.sort();
+        Ok(scene)
+    }
 
-        // TODO(Menno 28.12.2022) Once ordering is implemented, sort the scene here
-        // We need to sort the scene along the Z axis so that objects are drawn on top of each-other correctly
-        // scene
-        //     .0
-        //     .sort_by(|a, b| a..partial_cmp(&b.depth()).unwrap_or(Ordering::Less));
+    /// Sort the objects along the Z axis, so that they are drawn on top of each other correctly.
+    /// Objects in the same layer keep their order.
+    pub fn sort(&mut self) {
+        self.0.sort_by_key(Object::layer);
+    }
 
-        Ok(scene)
+    fn convert_background(level: &level::Level) -> Vec<Object> {
+        let background = &level.background;
+        let mut objects: Vec<Object> = vec![];
+
+        // The water spans the whole level, the terrain is drawn in front of it to form the banks
+        if let Some(water) = &background.water {
+            let x_coordinates = level
+                .vertices
+                .iter()
+                .chain(
+                    background
+                        .terrain
+                        .iter()
+                        .flat_map(|polyline| &polyline.points),
+                )
+                .map(|coordinates| coordinates.x);
+            let min_x = x_coordinates.clone().fold(f64::INFINITY, f64::min);
+            let max_x = x_coordinates.fold(f64::NEG_INFINITY, f64::max);
+            if min_x < max_x {
+                objects.push(Object::Background(Background {
+                    line: Line(
+                        Coordinates {
+                            x: min_x,
+                            y: water.level,
+                        },
+                        Coordinates {
+                            x: max_x,
+                            y: water.level,
+                        },
+                    ),
+                    color: water.color,
+                    is_filled: true,
+                }));
+            }
+        }
+
+        for (polylines, is_filled) in [
+            (&background.terrain, true),
+            (&background.decorations, false),
+        ] {
+            for polyline in polylines {
+                for pair in polyline.points.windows(2) {
+                    objects.push(Object::Background(Background {
+                        line: Line(Coordinates::new(&pair[0]), Coordinates::new(&pair[1])),
+                        color: polyline.color,
+                        is_filled,
+                    }));
+                }
+            }
+        }
+        objects
     }
 
     fn convert_vehicles(vehicles: &[level::Vehicle]) -> Result<Vec<Object>> {
         let mut objects: Vec<Object> = vec![];
         for vehicle in vehicles {
             objects.push(Object::Vehicle(Vehicle {
-                vehicle_type: Self::get_vehicle_type(&vehicle.name)?,
+                vehicle_type: vehicle_types::find(&vehicle.name)?,
                 position: Coordinates::new(&vehicle.position),
                 rotation: vehicle.rotation,
+                is_sunk: false,
             }));
         }
         Ok(objects)
     }
 
-    fn get_vehicle_type(name: &String) -> Result<VehicleType> {
-        match name.as_str() {
-            "car" => Ok(VehicleType::Car),
-            "bus" => Ok(VehicleType::Bus),
-            _ => Err(anyhow!("Not a vehicle type: {}", name)),
-        }
-    }
-
     fn convert_beams(
         vertices: &[level::Coordinates],
         edges: &[level::Edge],
@@ -192,6 +270,9 @@ impl Scene {
                 material,
                 line: Line(vertex_a, vertex_b),
                 is_static,
+                force: 0.0,
+                load_ratio: 0.0,
+                is_sunk: false,
             }));
         }
         Ok(objects)
@@ -210,6 +291,10 @@ impl Scene {
             objects.push(Object::Wire(Wire {
                 material,
                 line: Line(vertex_a, vertex_b),
+                force: 0.0,
+                load_ratio: 0.0,
+                sag: 0.0,
+                is_sunk: false,
             }));
         }
         Ok(objects)
@@ -219,10 +304,14 @@ impl Scene {
         index: &level::VertexIndex,
         vertices: &[level::Coordinates],
     ) -> Result<Coordinates> {
-        Ok(Coordinates::new(
-            vertices
-                .get(index.0)
-                .context("Could not find vertex at specified index")?,
-        ))
+        Ok(Coordinates::new(vertices.get(index.0).with_context(
+            || {
+                format!(
+                    "Could not find vertex {}, the level has {} vertices",
+                    index.0,
+                    vertices.len()
+                )
+            },
+        )?))
     }
 }
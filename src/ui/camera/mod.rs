@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod unittest;
+
+use crate::level::Level;
+use crate::scene::Coordinates;
+use tui::layout::Rect;
+
+/// How much taller a terminal cell is than it is wide
+const CELL_ASPECT_RATIO: f64 = 2.0;
+
+/// The space around the fitted part of the level, as a fraction of its size
+const PADDING: f64 = 0.1;
+
+/// The smallest width and height that is fitted, so a level with a single vertex can still be seen
+const MIN_FIT_SIZE: f64 = 1.0;
+
+/// The fraction of the view that a pan moves it by
+const PAN_STEP: f64 = 0.1;
+
+const ZOOM_STEP: f64 = 1.25;
+const MIN_ZOOM: f64 = 0.25;
+const MAX_ZOOM: f64 = 16.0;
+
+/// The part of the world that is shown
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Bounds {
+    pub x: [f64; 2],
+    pub y: [f64; 2],
+}
+
+impl Bounds {
+    fn width(&self) -> f64 {
+        self.x[1] - self.x[0]
+    }
+
+    fn height(&self) -> f64 {
+        self.y[1] - self.y[0]
+    }
+
+    fn center(&self) -> Coordinates {
+        Coordinates {
+            x: (self.x[0] + self.x[1]) / 2.0,
+            y: (self.y[0] + self.y[1]) / 2.0,
+        }
+    }
+}
+
+/// Decides which part of the world is shown in the world view.
+/// By default it shows the level's roads and anchors, which can be panned and zoomed, or it follows a vehicle.
+#[derive(Debug, Clone)]
+pub struct Camera {
+    fit: Bounds,
+    /// How far the view was panned away from the fitted part of the level
+    offset: Coordinates,
+    zoom: f64,
+    is_following_vehicle: bool,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Camera {
+            fit: Bounds {
+                x: [0.0, 3.0],
+                y: [0.0, 3.0],
+            },
+            offset: Coordinates { x: 0.0, y: 0.0 },
+            zoom: 1.0,
+            is_following_vehicle: false,
+        }
+    }
+}
+
+impl Camera {
+    /// Create a camera that fits the roads and anchors of a level, or all its vertices when it has neither
+    pub fn new(level: &Level) -> Self {
+        let mut indices: Vec<usize> = level
+            .road
+            .iter()
+            .flat_map(|edge| [edge.0 .0, edge.1 .0])
+            .chain(level.anchors.iter().map(|anchor| anchor.0))
+            .collect();
+        if indices.is_empty() {
+            indices = (0..level.vertices.len()).collect();
+        }
+        let points: Vec<Coordinates> = indices
+            .into_iter()
+            .filter_map(|index| level.vertices.get(index))
+            .map(|vertex| Coordinates {
+                x: vertex.x,
+                y: vertex.y,
+            })
+            .collect();
+        let Some(first) = points.first() else {
+            return Camera::default();
+        };
+
+        let mut fit = Bounds {
+            x: [first.x, first.x],
+            y: [first.y, first.y],
+        };
+        for point in &points {
+            fit.x = [fit.x[0].min(point.x), fit.x[1].max(point.x)];
+            fit.y = [fit.y[0].min(point.y), fit.y[1].max(point.y)];
+        }
+        let center = fit.center();
+        let half_width = fit.width().max(MIN_FIT_SIZE) * (0.5 + PADDING);
+        let half_height = fit.height().max(MIN_FIT_SIZE) * (0.5 + PADDING);
+        Camera {
+            fit: Bounds {
+                x: [center.x - half_width, center.x + half_width],
+                y: [center.y - half_height, center.y + half_height],
+            },
+            ..Default::default()
+        }
+    }
+
+    /// The part of the world to show in an area of the terminal, without stretching it.
+    /// When following a vehicle, the view is centered on the position of the vehicle.
+    pub fn bounds(&self, area: Rect, vehicle_position: Option<Coordinates>) -> Bounds {
+        // The width of the view divided by its height, in world units
+        let aspect_ratio =
+            area.width.max(1) as f64 / (area.height.max(1) as f64 * CELL_ASPECT_RATIO);
+        let (width, height) = if self.fit.width() / self.fit.height() > aspect_ratio {
+            (self.fit.width(), self.fit.width() / aspect_ratio)
+        } else {
+            (self.fit.height() * aspect_ratio, self.fit.height())
+        };
+        let (width, height) = (width / self.zoom, height / self.zoom);
+
+        let center = match vehicle_position {
+            Some(position) if self.is_following_vehicle => position,
+            _ => {
+                let center = self.fit.center();
+                Coordinates {
+                    x: center.x + self.offset.x,
+                    y: center.y + self.offset.y,
+                }
+            }
+        };
+        Bounds {
+            x: [center.x - width / 2.0, center.x + width / 2.0],
+            y: [center.y - height / 2.0, center.y + height / 2.0],
+        }
+    }
+
+    /// Move the view by a number of steps to the right and up, which stops following a vehicle
+    pub fn pan(&mut self, dx: i32, dy: i32) {
+        let step = self.fit.width().max(self.fit.height()) * PAN_STEP / self.zoom;
+        self.offset.x += dx as f64 * step;
+        self.offset.y += dy as f64 * step;
+        self.is_following_vehicle = false;
+    }
+
+    /// Zoom in for positive steps, and out for negative steps
+    pub fn zoom(&mut self, steps: i32) {
+        self.zoom = (self.zoom * ZOOM_STEP.powi(steps)).clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+
+    /// Show the fitted part of the level again
+    pub fn reset(&mut self) {
+        self.offset = Coordinates { x: 0.0, y: 0.0 };
+        self.zoom = 1.0;
+        self.is_following_vehicle = false;
+    }
+
+    pub fn toggle_follow_vehicle(&mut self) {
+        self.is_following_vehicle = !self.is_following_vehicle;
+    }
+
+    pub fn is_following_vehicle(&self) -> bool {
+        self.is_following_vehicle
+    }
+}
@@ -0,0 +1,123 @@
+use crate::level::{Coordinates, Edge, Level, VertexIndex};
+use crate::scene;
+use crate::ui::camera::{Bounds, Camera};
+use tui::layout::Rect;
+
+fn assert_bounds_eq(actual: Bounds, expected: Bounds) {
+    for (actual, expected) in actual
+        .x
+        .iter()
+        .chain(&actual.y)
+        .zip(expected.x.iter().chain(&expected.y))
+    {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "Expected {:?}, instead found {:?}",
+            expected,
+            actual
+        );
+    }
+}
+
+/// A level with a road from 0 to 4 at height 1, and an unused vertex far away
+fn example_level() -> Level {
+    Level {
+        vertices: vec![
+            Coordinates { x: 0.0, y: 1.0 },
+            Coordinates { x: 4.0, y: 1.0 },
+            Coordinates { x: 100.0, y: 100.0 },
+        ],
+        anchors: vec![VertexIndex(0)],
+        road: vec![Edge(VertexIndex(0), VertexIndex(1))],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn fit_level_test() {
+    let camera = Camera::new(&example_level());
+    // The road is 4 wide with 0.4 padding, and a minimal height of 1 with 0.1 padding,
+    // a view of 20 by 10 cells is as wide as it is high
+    assert_bounds_eq(
+        camera.bounds(Rect::new(0, 0, 20, 10), None),
+        Bounds {
+            x: [-0.4, 4.4],
+            y: [-1.4, 3.4],
+        },
+    );
+    // A very wide view shows more to the sides
+    assert_bounds_eq(
+        camera.bounds(Rect::new(5, 5, 100, 10), None),
+        Bounds {
+            x: [-1.0, 5.0],
+            y: [0.4, 1.6],
+        },
+    );
+
+    // An empty level shows the default view
+    assert_eq!(
+        Camera::new(&Level::default()).bounds(Rect::new(0, 0, 20, 10), None),
+        Bounds {
+            x: [0.0, 3.0],
+            y: [0.0, 3.0],
+        },
+    );
+}
+
+#[test]
+fn pan_and_zoom_test() {
+    let mut camera = Camera::new(&example_level());
+    let area = Rect::new(0, 0, 20, 10);
+    camera.zoom(1);
+    assert_bounds_eq(
+        camera.bounds(area, None),
+        Bounds {
+            x: [0.08, 3.92],
+            y: [-0.92, 2.92],
+        },
+    );
+    // Panning moves a tenth of the fitted size, which shrinks when zoomed in
+    camera.zoom(-1);
+    camera.pan(1, -2);
+    assert_bounds_eq(
+        camera.bounds(area, None),
+        Bounds {
+            x: [0.08, 4.88],
+            y: [-2.36, 2.44],
+        },
+    );
+    camera.reset();
+    assert_bounds_eq(
+        camera.bounds(area, None),
+        Bounds {
+            x: [-0.4, 4.4],
+            y: [-1.4, 3.4],
+        },
+    );
+}
+
+#[test]
+fn follow_vehicle_test() {
+    let mut camera = Camera::new(&example_level());
+    let area = Rect::new(0, 0, 20, 10);
+    let vehicle = Some(scene::Coordinates { x: 10.0, y: 0.0 });
+    assert_eq!(camera.bounds(area, vehicle), camera.bounds(area, None));
+
+    camera.toggle_follow_vehicle();
+    assert_bounds_eq(
+        camera.bounds(area, vehicle),
+        Bounds {
+            x: [7.6, 12.4],
+            y: [-2.4, 2.4],
+        },
+    );
+    // Without a vehicle the camera shows the level
+    assert_eq!(
+        camera.bounds(area, None),
+        Camera::new(&example_level()).bounds(area, None)
+    );
+
+    // Panning stops following
+    camera.pan(0, 0);
+    assert!(!camera.is_following_vehicle());
+}
@@ -9,12 +9,12 @@ use tui::widgets::canvas::{Canvas, Context};
 use tui::Frame;
 
 /// Trait for drawing icons on a canvas
-trait Icon {
+pub trait Icon {
     fn draw(context: &mut Context);
 }
 
 /// A start/stop icon
-struct PlayIcon;
+pub struct PlayIcon;
 impl Icon for PlayIcon {
     fn draw(context: &mut Context) {
         context.draw(&canvas::Rectangle {
@@ -8,7 +8,7 @@ mod mouse_area;
 mod popup;
 
 pub use focus_scope::FocusScope;
-pub use icon_button::IconButton;
+pub use icon_button::{Icon, IconButton, PlayIcon};
 pub use list_menu::{ListMenu, ListMenuItem};
 pub use mouse_area::MouseArea;
 pub use popup::Popup;
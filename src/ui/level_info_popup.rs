@@ -0,0 +1,128 @@
+use crate::level::Metadata;
+use crate::material::MaterialTable;
+use crate::ui::components::{FocusScope, Popup};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::backend::Backend;
+use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Paragraph, Wrap};
+use tui::Frame;
+
+/// A popup that introduces a level when it is loaded
+#[derive(Default)]
+pub struct LevelInfoPopup {
+    is_open: bool,
+    title: String,
+    metadata: Metadata,
+    /// The names of the materials the player may build with
+    allowed_materials: Vec<&'static str>,
+}
+
+impl FocusScope for LevelInfoPopup {
+    fn handle_key_event(&mut self, key_event: &KeyEvent) -> Result<bool> {
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Esc | KeyCode::Char(' ') => {
+                self.close();
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+impl Popup for LevelInfoPopup {
+    fn open(&mut self) {
+        self.is_open = true;
+    }
+
+    fn close(&mut self) {
+        self.is_open = false;
+    }
+
+    fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    fn calculate_inner_area(&self, outer_area: Rect) -> Rect {
+        let vertical_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(25),
+                    Constraint::Length(13),
+                    Constraint::Min(0),
+                ]
+                .as_ref(),
+            )
+            .split(outer_area);
+        let horizontal_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(
+                [
+                    Constraint::Percentage(25),
+                    Constraint::Percentage(50),
+                    Constraint::Percentage(25),
+                ]
+                .as_ref(),
+            )
+            .split(vertical_layout[1]);
+
+        horizontal_layout[1]
+    }
+
+    fn draw_inner<B: Backend>(&mut self, frame: &mut Frame<B>, inner_area: Rect) {
+        let metadata = &self.metadata;
+        let mut text: Vec<Spans> = vec![];
+        if let Some(author) = &metadata.author {
+            text.push(Spans::from(Span::styled(
+                format!("by {}", author),
+                Style::default().add_modifier(Modifier::ITALIC),
+            )));
+        }
+        if let Some(difficulty) = metadata.difficulty {
+            text.push(Spans::from(format!("Difficulty: {}", difficulty)));
+        }
+        if let Some(budget) = metadata.recommended_budget {
+            text.push(Spans::from(format!("Recommended budget: {:.0}", budget)));
+        }
+        text.push(Spans::from(format!(
+            "Materials: {}",
+            self.allowed_materials.join(", ")
+        )));
+        if !metadata.tags.is_empty() {
+            text.push(Spans::from(format!("Tags: {}", metadata.tags.join(", "))));
+        }
+        if let Some(description) = &metadata.description {
+            text.push(Spans::default());
+            text.push(Spans::from(description.as_str()));
+        }
+        text.push(Spans::default());
+        text.push(Spans::from("Press Enter to start building"));
+
+        let paragraph = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(self.title.as_str())
+                    .borders(Borders::ALL),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black))
+            .wrap(Wrap { trim: true });
+        frame.render_widget(paragraph, inner_area);
+    }
+}
+
+impl LevelInfoPopup {
+    /// Show the popup with the title, metadata and allowed materials of a level
+    pub fn show(&mut self, title: String, metadata: Metadata, materials: &MaterialTable) {
+        self.title = title;
+        self.metadata = metadata;
+        self.allowed_materials = materials
+            .iter()
+            .filter(|(_, properties)| properties.is_allowed)
+            .map(|(material, _)| material.name())
+            .collect();
+        self.open();
+    }
+}
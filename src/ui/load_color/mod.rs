@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod unittest;
+
+use tui::style::Color;
+
+/// The colors of members under increasing tension, from unloaded to breaking
+const TENSION_COLORS: [(u8, u8, u8); 3] = [(0, 255, 0), (255, 255, 0), (255, 0, 0)];
+
+/// The colors of members under increasing compression, from unloaded to breaking
+const COMPRESSION_COLORS: [(u8, u8, u8); 3] = [(0, 128, 255), (255, 0, 255), (255, 0, 0)];
+
+/// The heat map color of a member, which shows how close it is to breaking, and whether it is in tension
+pub fn load_color(force: f64, load_ratio: f64) -> Color {
+    let stops = if force >= 0.0 {
+        TENSION_COLORS
+    } else {
+        COMPRESSION_COLORS
+    };
+    // Blend between the two stops around the load ratio
+    let position = load_ratio.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+    let index = (position.floor() as usize).min(stops.len() - 2);
+    let fraction = position - index as f64;
+    let blend =
+        |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * fraction).round() as u8;
+    let (from, to) = (stops[index], stops[index + 1]);
+    Color::Rgb(
+        blend(from.0, to.0),
+        blend(from.1, to.1),
+        blend(from.2, to.2),
+    )
+}
@@ -0,0 +1,22 @@
+use crate::ui::load_color::load_color;
+use tui::style::Color;
+
+#[test]
+fn tension_test() {
+    assert_eq!(load_color(1.0, 0.0), Color::Rgb(0, 255, 0));
+    assert_eq!(load_color(1.0, 0.25), Color::Rgb(128, 255, 0));
+    assert_eq!(load_color(1.0, 0.5), Color::Rgb(255, 255, 0));
+    assert_eq!(load_color(1.0, 1.0), Color::Rgb(255, 0, 0));
+    // Members past their strength are about to break, which looks the same as at their strength
+    assert_eq!(load_color(1.0, 1.5), Color::Rgb(255, 0, 0));
+    // Unloaded members count as being in tension
+    assert_eq!(load_color(0.0, 0.0), Color::Rgb(0, 255, 0));
+}
+
+#[test]
+fn compression_test() {
+    assert_eq!(load_color(-1.0, 0.0), Color::Rgb(0, 128, 255));
+    assert_eq!(load_color(-1.0, 0.5), Color::Rgb(255, 0, 255));
+    assert_eq!(load_color(-1.0, 1.0), Color::Rgb(255, 0, 0));
+    assert_eq!(load_color(-1.0, 1.5), Color::Rgb(255, 0, 0));
+}
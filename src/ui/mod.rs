@@ -1,5 +1,9 @@
+mod camera;
 mod components;
+mod level_info_popup;
+mod load_color;
 mod main_menu;
+mod result_popup;
 mod scene_view;
 mod terminal_manager;
 mod world_menu;
@@ -12,7 +16,9 @@ use crate::ui::terminal_manager::TerminalManagerEvent;
 use anyhow::{Context, Result};
 use components::{FocusScope, MouseArea, Popup};
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent};
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use terminal_manager::TerminalManager;
 
 pub struct Gui {
@@ -20,6 +26,8 @@ pub struct Gui {
     terminal_manager: TerminalManager,
     main_menu: MainMenu,
     scene_view: SceneView,
+    frame_interval: Duration,
+    last_frame: Instant,
 }
 
 impl FocusScope for Gui {
@@ -56,21 +64,37 @@ impl MouseArea for Gui {
         if self.main_menu.is_open() {
             Ok(Some(&mut self.main_menu))
         } else {
-            Ok(None)
+            Ok(Some(&mut self.scene_view))
         }
     }
 }
 
 impl Gui {
+    /// Create the GUI, which ticks the simulation every `tick_interval` and redraws at most every `frame_interval`.
+    /// A replay is played back on the initial level, completed simulations are recorded to `record_path`.
     pub fn new(
         stop_token: Arc<StopToken>,
-        initial_level_path: Option<&std::path::PathBuf>,
+        initial_level_path: Option<&PathBuf>,
+        initial_solution_path: Option<&PathBuf>,
+        replay_path: Option<&PathBuf>,
+        record_path: Option<&PathBuf>,
+        tick_interval: Duration,
+        frame_interval: Duration,
     ) -> Result<Gui> {
+        let mut scene_view =
+            SceneView::new(initial_level_path, initial_solution_path, record_path)?;
+        if let Some(replay_path) = replay_path {
+            scene_view.load_replay(replay_path)?;
+        }
+
         Ok(Gui {
             stop_token: stop_token.clone(),
-            terminal_manager: TerminalManager::new().context("Can't setup terminal")?,
+            terminal_manager: TerminalManager::new(tick_interval)
+                .context("Can't setup terminal")?,
             main_menu: MainMenu::new(stop_token),
-            scene_view: SceneView::new(initial_level_path)?,
+            scene_view,
+            frame_interval,
+            last_frame: Instant::now(),
         })
     }
 
@@ -78,15 +102,16 @@ impl Gui {
         let mut draw_needed = true;
         while self.stop_token.keep_running() {
             match self.terminal_manager.next()? {
-                TerminalManagerEvent::TickEvent => {
-                    draw_needed |= self.scene_view.physics_tick()?;
+                TerminalManagerEvent::TickEvent(elapsed) => {
+                    draw_needed |= self.scene_view.physics_tick(elapsed)?;
                 }
                 TerminalManagerEvent::TerminalEvent(event) => {
                     draw_needed |= self.handle_terminal_event(event)?
                 }
             }
-            if draw_needed {
+            if draw_needed && self.last_frame.elapsed() >= self.frame_interval {
                 draw_needed = false;
+                self.last_frame = Instant::now();
                 self.terminal_manager.draw(|frame| {
                     self.scene_view.draw(frame);
                     if self.main_menu.is_open() {
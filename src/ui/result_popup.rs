@@ -0,0 +1,127 @@
+use crate::engine::{FailureReason, Outcome, SimulationResult};
+use crate::ui::components::{FocusScope, Popup};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::backend::Backend;
+use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::style::{Color, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Paragraph};
+use tui::Frame;
+
+/// A popup that shows the outcome of a completed simulation
+#[derive(Default)]
+pub struct ResultPopup {
+    is_open: bool,
+    result: Option<SimulationResult>,
+}
+
+impl FocusScope for ResultPopup {
+    fn handle_key_event(&mut self, key_event: &KeyEvent) -> Result<bool> {
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Esc | KeyCode::Char(' ') => {
+                self.close();
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+impl Popup for ResultPopup {
+    fn open(&mut self) {
+        self.is_open = true;
+    }
+
+    fn close(&mut self) {
+        self.is_open = false;
+    }
+
+    fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    fn calculate_inner_area(&self, outer_area: Rect) -> Rect {
+        let vertical_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(30),
+                    Constraint::Length(11),
+                    Constraint::Min(0),
+                ]
+                .as_ref(),
+            )
+            .split(outer_area);
+        let horizontal_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(
+                [
+                    Constraint::Percentage(30),
+                    Constraint::Percentage(40),
+                    Constraint::Percentage(30),
+                ]
+                .as_ref(),
+            )
+            .split(vertical_layout[1]);
+
+        horizontal_layout[1]
+    }
+
+    fn draw_inner<B: Backend>(&mut self, frame: &mut Frame<B>, inner_area: Rect) {
+        let Some(result) = &self.result else {
+            return;
+        };
+
+        let (title, message, color) = match &result.outcome {
+            Outcome::Success => (
+                "Level completed",
+                "All vehicles reached the goal!",
+                Color::Green,
+            ),
+            Outcome::Failure(FailureReason::VehicleDrowned) => {
+                ("Level failed", "A vehicle fell into the water", Color::Red)
+            }
+            Outcome::Failure(FailureReason::Timeout) => (
+                "Level failed",
+                "The vehicles did not reach the goal in time",
+                Color::Red,
+            ),
+        };
+
+        let statistics = &result.statistics;
+        let text = vec![
+            Spans::from(Span::styled(message, Style::default().fg(color))),
+            Spans::default(),
+            Spans::from(format!("Time: {:.1} s", statistics.elapsed_time)),
+            Spans::from(format!(
+                "Broken members: {}",
+                statistics.broken_member_count
+            )),
+            Spans::from(format!(
+                "Highest load: {:.0}%",
+                statistics.max_load_ratio * 100.0
+            )),
+            Spans::from(match result.budget {
+                Some(budget) => format!("Cost: {:.0} of {:.0}", result.cost, budget),
+                None => format!("Cost: {:.0}", result.cost),
+            }),
+            Spans::from(format!("Score: {}", result.score)),
+            Spans::default(),
+            Spans::from("Press Enter to continue"),
+        ];
+
+        let paragraph = Paragraph::new(text)
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .style(Style::default().fg(Color::White).bg(Color::Black));
+        frame.render_widget(paragraph, inner_area);
+    }
+}
+
+impl ResultPopup {
+    /// Show the popup with the specified result
+    pub fn show(&mut self, result: SimulationResult) {
+        self.result = Some(result);
+        self.open();
+    }
+}
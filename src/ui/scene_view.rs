@@ -1,24 +1,98 @@
-use crate::engine::{Engine, SimulationState};
+use crate::editor::Editor;
+use crate::engine::{Engine, SimulationEvent, SimulationState};
+use crate::level::{Level, Solution};
+use crate::replay::{self, Replay};
 use crate::savefile;
-use crate::ui::components::FocusScope;
+use crate::scene::{Beam, Object, Wire};
+use crate::ui::components::{FocusScope, MouseArea, Popup};
+use crate::ui::level_info_popup::LevelInfoPopup;
+use crate::ui::result_popup::ResultPopup;
 use crate::ui::world_menu::WorldMenu;
 use crate::ui::world_view::WorldView;
-use anyhow::Result;
-use std::cell::RefCell;
+use anyhow::{ensure, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::time::Duration;
+use tracing::{error, info};
 use tui::backend::Backend;
 use tui::layout::{Constraint, Direction, Layout};
 use tui::Frame;
 
 pub struct SceneView {
     engine: Rc<RefCell<Engine>>,
+    /// Builds the player's bridge, and remembers the edits so they can be undone
+    editor: Rc<RefCell<Editor>>,
     world_view: WorldView,
     world_menu: WorldMenu,
+    result_popup: ResultPopup,
+    level_info_popup: LevelInfoPopup,
+    broken_member_count: usize,
+    /// The level as it was loaded, without the player's solution
+    level: Level,
+    /// The player's solution, which is merged into the level that is simulated
+    solution: Solution,
+    /// Where the player's solution is saved from the editor
+    solution_path: PathBuf,
+    /// Whether an existing file at the solution path may be replaced,
+    /// which is only the case when the player loaded it or saved it before
+    may_replace_solution: bool,
+    /// The replay that is being played back, if any
+    replay: Option<Replay>,
+    /// Shared with the world menu, set when undoing or redoing changed the bridge the replay was recorded on
+    is_bridge_changed: Rc<Cell<bool>>,
+    /// Where to save a replay of every completed simulation
+    record_path: Option<PathBuf>,
 }
 
 impl FocusScope for SceneView {
+    fn handle_key_event(&mut self, key_event: &KeyEvent) -> Result<bool> {
+        if key_event.modifiers == KeyModifiers::CONTROL {
+            let step: fn(&mut Editor, &mut Level) -> bool = match key_event.code {
+                KeyCode::Char('z') => Editor::undo,
+                KeyCode::Char('y') => Editor::redo,
+                KeyCode::Char('s') => {
+                    match self.save_solution() {
+                        Ok(()) => info!("Saved solution to `{}`", self.solution_path.display()),
+                        Err(err) => error!("Could not save solution: {:#}", err),
+                    }
+                    return Ok(true);
+                }
+                _ => return Ok(false),
+            };
+            step_history(&self.engine, &self.editor, &self.is_bridge_changed, step);
+            return Ok(true);
+        }
+        self.drop_outdated_replay();
+        if self.world_view.handle_view_key(key_event) {
+            return Ok(true);
+        }
+        let Some(replay) = &self.replay else {
+            return Ok(false);
+        };
+
+        let step_count = self.engine.borrow().step_count();
+        let step_rate = self.engine.borrow().simulation_parameters().step_rate as u64;
+        let target_step = match key_event.code {
+            KeyCode::Char('.') => step_count + 1,
+            KeyCode::Char(',') => step_count.saturating_sub(1),
+            KeyCode::Char(']') => step_count + step_rate,
+            KeyCode::Char('[') => step_count.saturating_sub(step_rate),
+            KeyCode::Home => 0,
+            KeyCode::End => replay.step_count,
+            _ => return Ok(false),
+        };
+        self.seek(target_step.min(replay.step_count))?;
+        Ok(true)
+    }
+
     fn determine_focus(&mut self) -> Result<Option<&mut dyn FocusScope>> {
-        if self.world_view.is_edit_mode_active() {
+        if self.level_info_popup.is_open() {
+            Ok(Some(&mut self.level_info_popup))
+        } else if self.result_popup.is_open() {
+            Ok(Some(&mut self.result_popup))
+        } else if self.world_view.is_edit_mode_active() {
             Ok(Some(&mut self.world_view))
         } else {
             Ok(Some(&mut self.world_menu))
@@ -26,62 +100,333 @@ impl FocusScope for SceneView {
     }
 }
 
+/// Undo or redo an edit of the bridge, the simulation is reset to show the result
+fn step_history(
+    engine: &RefCell<Engine>,
+    editor: &RefCell<Editor>,
+    is_bridge_changed: &Cell<bool>,
+    step: fn(&mut Editor, &mut Level) -> bool,
+) {
+    let mut engine_ref = engine.borrow_mut();
+    let mut level = engine_ref.level().clone();
+    if step(&mut editor.borrow_mut(), &mut level) {
+        if let Err(err) = engine_ref.set_level(level) {
+            error!("Could not rebuild the level: {:#}", err);
+        }
+        engine_ref.reset_simulation();
+        is_bridge_changed.set(true);
+    }
+}
+
+impl MouseArea for SceneView {
+    fn determine_focus(&mut self, mouse_event: &MouseEvent) -> Result<Option<&mut dyn MouseArea>> {
+        // Popups cover the world
+        if self.level_info_popup.is_open() || self.result_popup.is_open() {
+            Ok(None)
+        } else if self.is_inside(mouse_event, self.world_view.area()) {
+            Ok(Some(&mut self.world_view))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 impl SceneView {
-    pub fn new(initial_level_path: Option<&std::path::PathBuf>) -> Result<SceneView> {
+    pub fn new(
+        initial_level_path: Option<&PathBuf>,
+        initial_solution_path: Option<&PathBuf>,
+        record_path: Option<&PathBuf>,
+    ) -> Result<SceneView> {
         let engine = Rc::new(RefCell::new(Engine::new()));
         let engine_clone_1 = Rc::clone(&engine);
         let engine_clone_2 = Rc::clone(&engine);
+        let engine_clone_3 = Rc::clone(&engine);
+        let engine_clone_4 = Rc::clone(&engine);
+        let engine_clone_5 = Rc::clone(&engine);
+        let editor = Rc::new(RefCell::new(Editor::new(&Level::default())));
+        let editor_clone_1 = Rc::clone(&editor);
+        let editor_clone_2 = Rc::clone(&editor);
+        let is_edit_mode_active = Rc::new(Cell::new(false));
+        let is_edit_mode_active_clone = Rc::clone(&is_edit_mode_active);
+        let is_bridge_changed = Rc::new(Cell::new(false));
+        let is_bridge_changed_clone_1 = Rc::clone(&is_bridge_changed);
+        let is_bridge_changed_clone_2 = Rc::clone(&is_bridge_changed);
         let mut new_scene_view = SceneView {
+            world_view: WorldView::new(Rc::clone(&engine), Rc::clone(&editor), is_edit_mode_active),
             engine,
-            world_view: WorldView::default(),
+            editor,
             world_menu: WorldMenu::new(
                 Box::from(move || {
                     let mut engine_ref = engine_clone_1.borrow_mut();
-                    match engine_ref.simulation_state() {
-                        SimulationState::Paused => engine_ref.start_simulation(),
-                        SimulationState::Running => engine_ref.pause_simulation(),
-                        SimulationState::Cleared => engine_ref.start_simulation(),
-                        _ => {}
+                    let result = match engine_ref.simulation_state() {
+                        SimulationState::Paused | SimulationState::Cleared => {
+                            engine_ref.start_simulation()
+                        }
+                        SimulationState::Running => {
+                            engine_ref.pause_simulation();
+                            Ok(())
+                        }
+                        _ => Ok(()),
+                    };
+                    if let Err(err) = result {
+                        error!("Could not start the simulation: {:#}", err);
                     }
                 }),
                 Box::from(move || engine_clone_2.borrow_mut().reset_simulation()),
+                Box::from(move || {
+                    // The bridge is edited as it was built, not as it is being simulated
+                    engine_clone_3.borrow_mut().reset_simulation();
+                    is_edit_mode_active_clone.set(true);
+                }),
+                Box::from(move || {
+                    step_history(
+                        &engine_clone_4,
+                        &editor_clone_1,
+                        &is_bridge_changed_clone_1,
+                        Editor::undo,
+                    )
+                }),
+                Box::from(move || {
+                    step_history(
+                        &engine_clone_5,
+                        &editor_clone_2,
+                        &is_bridge_changed_clone_2,
+                        Editor::redo,
+                    )
+                }),
             ),
+            result_popup: ResultPopup::default(),
+            level_info_popup: LevelInfoPopup::default(),
+            broken_member_count: 0,
+            level: Level::default(),
+            solution: Solution::default(),
+            solution_path: PathBuf::new(),
+            may_replace_solution: false,
+            replay: None,
+            is_bridge_changed,
+            record_path: record_path.cloned(),
         };
 
-        if initial_level_path.is_some() {
-            new_scene_view.load_level(initial_level_path.unwrap())?;
+        if let Some(level_path) = initial_level_path {
+            new_scene_view.load_level(level_path, initial_solution_path)?;
         }
 
         Ok(new_scene_view)
     }
 
-    pub fn load_level(&mut self, level_path: &std::path::PathBuf) -> Result<()> {
+    /// Load a level, and optionally a solution that was made for it.
+    /// Without a solution, the player's bridge is saved to the saves directory.
+    pub fn load_level(
+        &mut self,
+        level_path: &PathBuf,
+        solution_path: Option<&PathBuf>,
+    ) -> Result<()> {
+        let (level, level_hash) = savefile::load_with_hash(level_path)?;
+        let solution = match solution_path {
+            Some(solution_path) => savefile::load_solution(solution_path, level_hash)?,
+            None => Solution {
+                level_path: level_path.clone(),
+                level_hash,
+                ..Default::default()
+            },
+        };
+
+        // Levels without a title are named after their file
+        let title = level.metadata.title.clone().unwrap_or_else(|| {
+            level_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+        self.world_menu.set_title(title.clone());
+        self.level_info_popup
+            .show(title, level.metadata.clone(), &level.materials);
+        self.world_view.set_sky_color(level.background.sky_color);
+        self.world_view.reset(&level);
+
+        self.solution_path = match solution_path {
+            Some(solution_path) => solution_path.clone(),
+            None => PathBuf::from("saves").join(level_path.file_name().unwrap_or_default()),
+        };
+        self.may_replace_solution = solution_path.is_some();
+        self.level = level;
+        self.replay = None;
+        self.set_solution(solution)
+    }
+
+    /// Merge a solution into the loaded level, and simulate the result
+    fn set_solution(&mut self, solution: Solution) -> Result<()> {
+        let mut engine_ref = self.engine.borrow_mut();
+        engine_ref.set_level(savefile::merge_solution(self.level.clone(), &solution)?)?;
+        self.solution = solution;
+        Ok(())
+    }
+
+    /// The player's solution as it is currently built in the editor
+    fn current_solution(&self) -> Result<Solution> {
+        savefile::extract_solution(
+            &self.level,
+            self.engine.borrow().level(),
+            self.solution.level_path.clone(),
+            self.solution.level_hash,
+        )
+    }
+
+    fn save_solution(&mut self) -> Result<()> {
+        ensure!(
+            self.may_replace_solution || !self.solution_path.exists(),
+            "`{}` already exists, load it with --solution to replace it",
+            self.solution_path.display()
+        );
+        if let Some(directory) = self.solution_path.parent() {
+            std::fs::create_dir_all(directory)?;
+        }
+        savefile::save_solution(&self.current_solution()?, &self.solution_path)?;
+        self.may_replace_solution = true;
+        Ok(())
+    }
+
+    /// Play back a replay that was recorded on the loaded level, starting paused at the first step
+    pub fn load_replay(&mut self, replay_path: &PathBuf) -> Result<()> {
+        let replay = replay::load(replay_path)?;
+        ensure!(
+            replay.solution.level_hash == self.solution.level_hash,
+            "Replay `{}` was recorded on a different level",
+            replay_path.display()
+        );
+
+        self.set_solution(replay.solution.clone())?;
         self.engine
             .borrow_mut()
-            .set_level(savefile::load(level_path)?)?;
+            .set_simulation_parameters(replay.parameters)?;
+        self.replay = Some(replay);
+        self.seek(0)
+    }
+
+    /// Jump to a step of the replay, pausing it
+    fn seek(&mut self, target_step: u64) -> Result<()> {
+        self.engine.borrow_mut().seek(target_step)?;
+        self.update_replay_status();
+        Ok(())
+    }
+
+    fn update_replay_status(&mut self) {
+        if let Some(replay) = &self.replay {
+            let engine_ref = self.engine.borrow();
+            let time_step = engine_ref.simulation_parameters().time_step();
+            self.world_menu.set_status(format!(
+                "Replay {:.2}/{:.2} s",
+                engine_ref.step_count() as f64 * time_step,
+                replay.step_count as f64 * time_step
+            ));
+        }
+    }
+
+    /// Save a replay of the simulation that just completed
+    fn record(&self, path: &PathBuf) -> Result<()> {
+        let solution = self.current_solution()?;
+        let engine_ref = self.engine.borrow();
+        replay::save(
+            &Replay {
+                solution,
+                parameters: *engine_ref.simulation_parameters(),
+                step_count: engine_ref.step_count(),
+            },
+            path,
+        )?;
+        info!("Saved replay to `{}`", path.display());
         Ok(())
     }
 
-    pub fn physics_tick(&mut self) -> Result<bool> {
+    /// Stop playing back the replay once the bridge it was recorded on is edited, undone or redone
+    fn drop_outdated_replay(&mut self) {
+        let is_bridge_changed = self.is_bridge_changed.take();
+        if (is_bridge_changed || self.world_view.is_edit_mode_active()) && self.replay.is_some() {
+            self.replay = None;
+            self.world_menu.set_status(String::new());
+        }
+    }
+
+    pub fn physics_tick(&mut self, elapsed: Duration) -> Result<bool> {
+        self.drop_outdated_replay();
         let mut engine_ref = self.engine.borrow_mut();
         match engine_ref.simulation_state() {
             SimulationState::Running => {
-                engine_ref.simulate_scene_tick()?;
+                for event in engine_ref.simulate_scene_tick(elapsed)? {
+                    match event {
+                        SimulationEvent::MemberBroken(object) => {
+                            if let Object::Beam(Beam {
+                                force, load_ratio, ..
+                            })
+                            | Object::Wire(Wire {
+                                force, load_ratio, ..
+                            }) = object
+                            {
+                                info!(
+                                    "Member broke under a force of {:.1}, {:.0}% of its strength",
+                                    force,
+                                    load_ratio * 100.0
+                                );
+                            }
+                            self.broken_member_count += 1;
+                            self.world_menu.set_status(format!(
+                                "{} member(s) broke",
+                                self.broken_member_count
+                            ));
+                        }
+                    }
+                }
+                if let Some(replay) = &self.replay {
+                    // Playback stops where the recording ended
+                    if engine_ref.step_count() >= replay.step_count {
+                        engine_ref.pause_simulation();
+                    }
+                    drop(engine_ref);
+                    self.update_replay_status();
+                } else if let SimulationState::Completed(result) = engine_ref.simulation_state() {
+                    self.result_popup.show(result.clone());
+                    drop(engine_ref);
+                    if let Some(path) = &self.record_path {
+                        if let Err(err) = self.record(path) {
+                            error!("Could not record replay: {:#}", err);
+                        }
+                    }
+                }
+                Ok(true)
+            }
+            SimulationState::Cleared if self.broken_member_count > 0 => {
+                // The simulation was reset, so the broken members are back in place
+                self.broken_member_count = 0;
+                self.world_menu.set_status(String::new());
+                drop(engine_ref);
+                self.update_replay_status();
                 Ok(true)
             }
             _ => Ok(false),
         }
     }
 
-    pub fn draw<B: Backend>(&self, frame: &mut Frame<B>) {
+    pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .margin(1)
             .constraints([Constraint::Percentage(85), Constraint::Percentage(15)].as_ref())
             .split(frame.size());
 
-        self.world_view
-            .draw(self.engine.borrow_mut().get_scene(), frame, chunks[0]);
-        self.world_menu.draw(frame, chunks[1])
+        let (cost, budget) = {
+            let engine_ref = self.engine.borrow();
+            (engine_ref.bridge_cost(), engine_ref.budget())
+        };
+        self.world_menu.set_cost(cost, budget);
+        self.world_menu
+            .set_heat_map_legend_visible(self.world_view.is_heat_map_active());
+        self.world_view.draw(frame, chunks[0]);
+        self.world_menu.draw(frame, chunks[1]);
+        if self.result_popup.is_open() {
+            self.result_popup.draw(frame);
+        }
+        if self.level_info_popup.is_open() {
+            self.level_info_popup.draw(frame);
+        }
     }
 }
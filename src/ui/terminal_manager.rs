@@ -12,11 +12,13 @@ use tui::{backend::CrosstermBackend, Frame, Terminal};
 
 pub enum TerminalManagerEvent {
     TerminalEvent(event::Event),
-    TickEvent,
+    /// A periodic tick, containing the time that passed since the previous tick
+    TickEvent(Duration),
 }
 
 pub struct TerminalManager {
     terminal: Terminal<CrosstermBackend<io::Stdout>>,
+    tick_interval: Duration,
     last_tick: Instant,
 }
 
@@ -34,7 +36,7 @@ impl Drop for TerminalManager {
 }
 
 impl TerminalManager {
-    pub fn new() -> Result<TerminalManager> {
+    pub fn new(tick_interval: Duration) -> Result<TerminalManager> {
         enable_raw_mode().context("Can not enable terminal raw mode")?;
         let mut stdout = io::stdout();
         execute!(stdout, EnterAlternateScreen, EnableMouseCapture)
@@ -44,22 +46,23 @@ impl TerminalManager {
 
         Ok(TerminalManager {
             terminal,
+            tick_interval,
             last_tick: Instant::now(),
         })
     }
 
     pub fn next(&mut self) -> Result<TerminalManagerEvent> {
         loop {
-            let tick_rate = Duration::from_millis(250);
-
             // check if it's time for a physics tick
-            if self.last_tick.elapsed() >= tick_rate {
+            let elapsed = self.last_tick.elapsed();
+            if elapsed >= self.tick_interval {
                 self.last_tick = Instant::now();
-                return Ok(TerminalManagerEvent::TickEvent);
+                return Ok(TerminalManagerEvent::TickEvent(elapsed));
             }
 
             // wait for event or timeout
-            let timeout = tick_rate
+            let timeout = self
+                .tick_interval
                 .checked_sub(self.last_tick.elapsed())
                 .unwrap_or_else(|| Duration::from_secs(0));
             if crossterm::event::poll(timeout)? {
@@ -1,15 +1,22 @@
-use crate::ui::components::{FocusScope, IconButton};
+use crate::ui::components::{FocusScope, Icon, IconButton, PlayIcon};
+use crate::ui::load_color::load_color;
 use anyhow::Context;
 use crossterm::event::{KeyCode, KeyEvent};
 use tui::backend::Backend;
 use tui::layout::{Constraint, Direction, Layout, Rect};
-use tui::style::Color;
-use tui::widgets::{Block, Borders};
+use tui::style::{Color, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Paragraph};
 use tui::Frame;
 
 pub struct WorldMenu {
     focused_button_idx: Option<usize>,
     simulation_controls: Vec<IconButton>,
+    title: String,
+    status: String,
+    cost: f64,
+    budget: Option<f64>,
+    is_heat_map_legend_visible: bool,
 }
 
 impl FocusScope for WorldMenu {
@@ -61,21 +68,16 @@ impl WorldMenu {
     pub fn new(
         simulation_start_pause_func: Box<dyn FnMut()>,
         simulation_reset_func: Box<dyn FnMut()>,
+        edit_func: Box<dyn FnMut()>,
+        undo_func: Box<dyn FnMut()>,
+        redo_func: Box<dyn FnMut()>,
     ) -> Self {
         WorldMenu {
             focused_button_idx: Some(0),
             simulation_controls: vec![
                 IconButton::new(
                     "Start/Pause (1)".to_string(),
-                    |context| {
-                        context.draw(&tui::widgets::canvas::Rectangle {
-                            x: -0.5,
-                            y: -0.5,
-                            width: 1.0,
-                            height: 1.0,
-                            color: Color::Red,
-                        })
-                    },
+                    PlayIcon::draw,
                     simulation_start_pause_func,
                 ),
                 IconButton::new(
@@ -91,12 +93,104 @@ impl WorldMenu {
                     },
                     simulation_reset_func,
                 ),
+                IconButton::new(
+                    "Edit (3)".to_string(),
+                    |context| {
+                        context.draw(&tui::widgets::canvas::Line {
+                            x1: -0.5,
+                            y1: -0.5,
+                            x2: 0.5,
+                            y2: 0.5,
+                            color: Color::Yellow,
+                        });
+                    },
+                    edit_func,
+                ),
+                IconButton::new("Undo (Ctrl+Z)".to_string(), Self::draw_undo_icon, undo_func),
+                IconButton::new("Redo (Ctrl+Y)".to_string(), Self::draw_redo_icon, redo_func),
             ],
+            title: "World Menu".to_string(),
+            status: String::new(),
+            cost: 0.0,
+            budget: None,
+            is_heat_map_legend_visible: false,
+        }
+    }
+
+    /// An arrow pointing to the left
+    fn draw_undo_icon(context: &mut tui::widgets::canvas::Context) {
+        for (x1, y1, x2, y2) in [
+            (0.5, 0.0, -0.5, 0.0),
+            (-0.5, 0.0, 0.0, 0.5),
+            (-0.5, 0.0, 0.0, -0.5),
+        ] {
+            context.draw(&tui::widgets::canvas::Line {
+                x1,
+                y1,
+                x2,
+                y2,
+                color: Color::Yellow,
+            });
         }
     }
 
+    /// An arrow pointing to the right
+    fn draw_redo_icon(context: &mut tui::widgets::canvas::Context) {
+        for (x1, y1, x2, y2) in [
+            (-0.5, 0.0, 0.5, 0.0),
+            (0.5, 0.0, 0.0, 0.5),
+            (0.5, 0.0, 0.0, -0.5),
+        ] {
+            context.draw(&tui::widgets::canvas::Line {
+                x1,
+                y1,
+                x2,
+                y2,
+                color: Color::Yellow,
+            });
+        }
+    }
+
+    /// Set the construction cost of the bridge and the level's budget, which are shown next to the controls
+    pub fn set_cost(&mut self, cost: f64, budget: Option<f64>) {
+        self.cost = cost;
+        self.budget = budget;
+    }
+
+    /// Show which colors the heat map uses for the loads of members
+    pub fn set_heat_map_legend_visible(&mut self, is_visible: bool) {
+        self.is_heat_map_legend_visible = is_visible;
+    }
+
+    /// A line that shows the heat map colors of a direction of load, from unloaded to breaking
+    fn heat_map_legend_line(label: &str, force: f64) -> Spans<'static> {
+        const STEP_COUNT: usize = 8;
+        let mut spans = vec![Span::raw(format!("{:<12}0% ", label))];
+        spans.extend((0..=STEP_COUNT).map(|step| {
+            let load_ratio = step as f64 / STEP_COUNT as f64;
+            Span::styled("█", Style::default().fg(load_color(force, load_ratio)))
+        }));
+        spans.push(Span::raw(" 100%"));
+        Spans::from(spans)
+    }
+
+    /// Set the name of the level, which is shown as the menu's title
+    pub fn set_title(&mut self, title: String) {
+        self.title = title;
+    }
+
+    /// Set a short message that is shown in the menu's title, an empty string hides it
+    pub fn set_status(&mut self, status: String) {
+        self.status = status;
+    }
+
     pub(crate) fn draw<B: Backend>(&self, frame: &mut Frame<B>, area: Rect) {
-        let block = Block::default().title("World Menu").borders(Borders::ALL);
+        let title = if self.status.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{} - {}", self.title, self.status)
+        };
+        let block = Block::default().title(title).borders(Borders::ALL);
         frame.render_widget(block, area);
 
         // TODO(Menno 02.01.2023) These constraints should probably be based on fixed sizes, or a square ratio
@@ -112,5 +206,23 @@ impl WorldMenu {
         for (i, button) in self.simulation_controls.iter().enumerate() {
             button.draw(frame, button_layout[i], self.focused_button_idx == Some(i));
         }
+
+        let cost = match self.budget {
+            Some(budget) if self.cost > budget => Span::styled(
+                format!("Cost: {:.0} / {:.0}, over budget", self.cost, budget),
+                Style::default().fg(Color::Red),
+            ),
+            Some(budget) => Span::raw(format!("Cost: {:.0} / {:.0}", self.cost, budget)),
+            None => Span::raw(format!("Cost: {:.0}", self.cost)),
+        };
+        let mut lines = vec![Spans::from(cost)];
+        if self.is_heat_map_legend_visible {
+            lines.push(Self::heat_map_legend_line("Tension", 1.0));
+            lines.push(Self::heat_map_legend_line("Compression", -1.0));
+        }
+        frame.render_widget(
+            Paragraph::new(lines),
+            button_layout[self.simulation_controls.len()],
+        );
     }
 }
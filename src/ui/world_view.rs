@@ -1,12 +1,20 @@
-use crate::scene::{BeamMaterial, Coordinates, Line, Object, Scene, VehicleType, WireMaterial};
-use crate::ui::components::FocusScope;
-use crossterm::event::{KeyCode, KeyEvent};
-use euclid;
+use crate::editor::{self, Editor};
+use crate::engine::Engine;
+use crate::level::{self, Edge, Level};
+use crate::material::Material;
+use crate::scene::{Background, Coordinates, Line, Object, Vehicle};
+use crate::ui::camera::{Bounds, Camera};
+use crate::ui::components::{FocusScope, MouseArea};
+use crate::ui::load_color::load_color;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use iterwindows::IterArrayWindows;
-use tracing::error;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 use tui::backend::Backend;
 use tui::layout::Rect;
-use tui::style::Color;
+use tui::style::{Color, Style};
+use tui::text::Span;
 use tui::widgets::canvas;
 use tui::widgets::canvas::{Canvas, Context};
 use tui::Frame;
@@ -17,65 +25,414 @@ struct VehicleSpace;
 type VehiclePoint = euclid::Point2D<f64, VehicleSpace>;
 type VehiclePosition = euclid::Transform2D<f64, VehicleSpace, WorldSpace>;
 
-#[derive(Default)]
+/// The color of everything that fell into the water
+const SUNK_COLOR: Color = Color::Blue;
+
+/// The color of the editor's cursor and selected vertex
+const CURSOR_COLOR: Color = Color::Magenta;
+
+/// The color of the vertex or member the cursor points at
+const HOVER_COLOR: Color = Color::LightCyan;
+
+const EDIT_HELP: &str =
+    "Arrows: move, Enter/click: place, drag: build, Delete/right click: remove, Tab: material, M: change material, Ctrl+Z/Y: undo/redo, Ctrl+S: save, Esc: exit";
+
 pub struct WorldView {
-    is_edit_mode_active: bool,
+    engine: Rc<RefCell<Engine>>,
+    /// Shared with the world menu, which can switch to edit mode
+    is_edit_mode_active: Rc<Cell<bool>>,
+    /// Shared with the world menu, which can undo and redo edits
+    editor: Rc<RefCell<Editor>>,
+    /// Explains why the last edit was refused
+    message: String,
+    sky_color: Option<level::Color>,
+    /// Where the left mouse button was pressed, to build a member when it is released elsewhere
+    press_position: Option<level::Coordinates>,
+    camera: Camera,
+    /// Whether members are colored by their load instead of by their material
+    is_heat_map_active: bool,
+    /// The part of the world that was last drawn
+    bounds: Bounds,
+    area: Rect,
 }
 
 impl FocusScope for WorldView {
-    fn handle_key_event(&mut self, key_event: &KeyEvent) -> anyhow::Result<bool> {
+    fn handle_key_event(&mut self, key_event: &KeyEvent) -> Result<bool> {
+        if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+            // Leave shortcuts like undo and redo to the scene view
+            return Ok(false);
+        }
         match key_event.code {
+            KeyCode::Enter | KeyCode::Char(' ') => return self.edit(Editor::place),
+            KeyCode::Delete | KeyCode::Backspace => return self.edit(Editor::delete),
+            KeyCode::Char('m') => return self.edit(Editor::change_material),
+            _ => {}
+        }
+
+        let mut editor = self.editor.borrow_mut();
+        match key_event.code {
+            KeyCode::Left => editor.move_cursor(-1, 0),
+            KeyCode::Right => editor.move_cursor(1, 0),
+            KeyCode::Up => editor.move_cursor(0, 1),
+            KeyCode::Down => editor.move_cursor(0, -1),
+            KeyCode::Tab => editor.next_material(&self.engine.borrow().level().materials),
             KeyCode::Esc => {
-                self.is_edit_mode_active = false;
+                // Escape first lets go of the selected vertex, and then leaves edit mode
+                if editor.selected_vertex().is_some() {
+                    editor.cancel();
+                } else {
+                    self.is_edit_mode_active.set(false);
+                }
+            }
+            _ => return Ok(false),
+        }
+        self.message.clear();
+        Ok(true)
+    }
+}
+
+impl MouseArea for WorldView {
+    fn handle_mouse_event(&mut self, mouse_event: &MouseEvent) -> Result<bool> {
+        match mouse_event.kind {
+            MouseEventKind::ScrollUp => {
+                self.camera.zoom(1);
+                return Ok(true);
+            }
+            MouseEventKind::ScrollDown => {
+                self.camera.zoom(-1);
+                return Ok(true);
+            }
+            _ if !self.is_edit_mode_active() => return Ok(false),
+            _ => {}
+        }
+
+        let position = self.to_world(mouse_event.column, mouse_event.row);
+        match mouse_event.kind {
+            MouseEventKind::Moved | MouseEventKind::Drag(MouseButton::Left) => {
+                self.editor.borrow_mut().set_cursor(position);
                 Ok(true)
             }
+            MouseEventKind::Down(MouseButton::Left) => {
+                let mut editor = self.editor.borrow_mut();
+                editor.set_cursor(position);
+                self.press_position = Some(editor.cursor().clone());
+                drop(editor);
+                // Every drag builds a member from where it started, not from the last member's end
+                self.edit(Editor::start_member)
+            }
+            MouseEventKind::Up(MouseButton::Left) => {
+                let mut editor = self.editor.borrow_mut();
+                editor.set_cursor(position);
+                // Releasing where the button was pressed was just a click, which already placed
+                if self.press_position.take().as_ref() == Some(editor.cursor()) {
+                    return Ok(true);
+                }
+                drop(editor);
+                self.edit(Editor::place)
+            }
+            MouseEventKind::Down(MouseButton::Right) => {
+                self.editor.borrow_mut().set_cursor(position);
+                self.edit(Editor::delete)
+            }
             _ => Ok(false),
         }
     }
 }
 
 impl WorldView {
+    pub(crate) fn new(
+        engine: Rc<RefCell<Engine>>,
+        editor: Rc<RefCell<Editor>>,
+        is_edit_mode_active: Rc<Cell<bool>>,
+    ) -> Self {
+        WorldView {
+            engine,
+            is_edit_mode_active,
+            editor,
+            message: String::new(),
+            sky_color: None,
+            press_position: None,
+            camera: Camera::default(),
+            is_heat_map_active: false,
+            bounds: Camera::default().bounds(Rect::default(), None),
+            area: Rect::default(),
+        }
+    }
+
+    pub(crate) fn area(&self) -> Rect {
+        self.area
+    }
+
     pub(crate) fn is_edit_mode_active(&self) -> bool {
-        self.is_edit_mode_active
-    }
-
-    pub(crate) fn draw<B: Backend>(&self, scene: &Scene, frame: &mut Frame<B>, area: Rect) {
-        let canvas = Canvas::default()
-            // TODO(Menno 28.12.2022) Calculate bounding box of scene automatically, which should possibly only care about roads
-            .x_bounds([0.0, 3.0])
-            .y_bounds([0.0, 3.0])
-            .paint(|ctx| {
-                for object in &scene.0 {
-                    match object {
-                        Object::Wire(wire) => {
-                            let color = match wire.material {
-                                WireMaterial::Steel => Color::Gray,
-                            };
+        self.is_edit_mode_active.get()
+    }
+
+    /// Start viewing and editing a newly loaded level, the level should not contain the player's solution yet
+    pub(crate) fn reset(&mut self, level: &Level) {
+        *self.editor.borrow_mut() = Editor::new(level);
+        self.message.clear();
+        self.camera = Camera::new(level);
+    }
+
+    pub(crate) fn is_heat_map_active(&self) -> bool {
+        self.is_heat_map_active
+    }
+
+    /// Pan with WASD, zoom with plus and minus, go back to the starting view of the roads and anchors with 0, and follow a vehicle with F.
+    /// H toggles the heat map. Returns whether the key was used.
+    pub(crate) fn handle_view_key(&mut self, key_event: &KeyEvent) -> bool {
+        match key_event.code {
+            KeyCode::Char('w') => self.camera.pan(0, 1),
+            KeyCode::Char('a') => self.camera.pan(-1, 0),
+            KeyCode::Char('s') => self.camera.pan(0, -1),
+            KeyCode::Char('d') => self.camera.pan(1, 0),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.camera.zoom(1),
+            KeyCode::Char('-') => self.camera.zoom(-1),
+            KeyCode::Char('0') => self.camera.reset(),
+            KeyCode::Char('f') => self.camera.toggle_follow_vehicle(),
+            KeyCode::Char('h') => self.is_heat_map_active = !self.is_heat_map_active,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Apply an edit to the engine's level, and rebuild its scene when the level changed.
+    /// Edits that are refused are explained to the player instead.
+    fn edit(&mut self, operation: fn(&mut Editor, &mut Level) -> Result<bool>) -> Result<bool> {
+        let mut engine_ref = self.engine.borrow_mut();
+        let mut level = engine_ref.level().clone();
+        self.message = match operation(&mut self.editor.borrow_mut(), &mut level) {
+            Ok(true) => {
+                engine_ref.set_level(level)?;
+                String::new()
+            }
+            Ok(false) => String::new(),
+            Err(err) => format!("{:#}", err),
+        };
+        Ok(true)
+    }
+
+    /// Set the color behind the scene, or none to use the terminal's background
+    pub(crate) fn set_sky_color(&mut self, sky_color: Option<level::Color>) {
+        self.sky_color = sky_color;
+    }
+
+    /// Convert the center of a terminal cell in the view to world coordinates
+    fn to_world(&self, column: u16, row: u16) -> level::Coordinates {
+        let (area, bounds) = (self.area, self.bounds);
+        let x_fraction = (column.saturating_sub(area.x) as f64 + 0.5) / area.width.max(1) as f64;
+        let y_fraction = (row.saturating_sub(area.y) as f64 + 0.5) / area.height.max(1) as f64;
+        level::Coordinates {
+            x: bounds.x[0] + x_fraction * (bounds.x[1] - bounds.x[0]),
+            // Rows count downwards, while world coordinates count upwards
+            y: bounds.y[1] - y_fraction * (bounds.y[1] - bounds.y[0]),
+        }
+    }
+
+    pub(crate) fn draw<B: Backend>(&mut self, frame: &mut Frame<B>, area: Rect) {
+        self.area = area;
+        let engine_ref = self.engine.borrow();
+        let scene = engine_ref.get_scene();
+        let vehicle_position = scene.0.iter().find_map(|object| match object {
+            Object::Vehicle(vehicle) => Some(vehicle.position),
+            _ => None,
+        });
+        self.bounds = self.camera.bounds(area, vehicle_position);
+        let (x_bounds, y_bounds) = (self.bounds.x, self.bounds.y);
+        // Canvas cells are drawn with braille characters, which are two dots wide
+        let dot_width = (x_bounds[1] - x_bounds[0]) / (area.width.max(1) as f64 * 2.0);
+        let row_height = (y_bounds[1] - y_bounds[0]) / area.height.max(1) as f64;
+
+        let mut canvas = Canvas::default().x_bounds(x_bounds).y_bounds(y_bounds);
+        if let Some(sky_color) = self.sky_color {
+            canvas = canvas.background_color(Self::tui_color(sky_color));
+        }
+        let canvas = canvas.paint(|ctx| {
+            // The scene is sorted from back to front, so later objects are drawn over earlier ones
+            for object in &scene.0 {
+                match object {
+                    Object::Background(background) => {
+                        Self::draw_background(ctx, background, y_bounds[0], dot_width)
+                    }
+                    Object::Wire(wire) => {
+                        let color = if wire.is_sunk {
+                            SUNK_COLOR
+                        } else if self.is_heat_map_active {
+                            load_color(wire.force, wire.load_ratio)
+                        } else {
+                            Self::material_color(wire.material.into())
+                        };
+                        if wire.sag > 0.0 {
+                            Self::draw_sagging_line(ctx, &wire.line, wire.sag, color);
+                        } else {
                             Self::draw_line(ctx, &wire.line, color);
                         }
-                        Object::Beam(beam) => {
-                            let color = match beam.material {
-                                BeamMaterial::Wood => Color::Red,
-                                BeamMaterial::Steel => Color::DarkGray,
-                                BeamMaterial::Road => Color::Black,
-                            };
-                            Self::draw_line(ctx, &beam.line, color);
-                        }
-                        Object::Vehicle(vehicle) => match vehicle.vehicle_type {
-                            VehicleType::Bus => {
-                                //TODO(Menno 06.11.2023) Add bus rendering
-                                error!("Bus rendering not implemented");
-                            }
-                            VehicleType::Car => {
-                                Self::draw_car(ctx, vehicle.position, vehicle.rotation)
-                            }
-                        },
+                    }
+                    Object::Beam(beam) => {
+                        let color = if beam.is_sunk {
+                            SUNK_COLOR
+                        } else if self.is_heat_map_active && !beam.is_static {
+                            // Static beams never carry any load
+                            load_color(beam.force, beam.load_ratio)
+                        } else {
+                            Self::material_color(beam.material.into())
+                        };
+                        Self::draw_line(ctx, &beam.line, color);
+                    }
+                    Object::Vehicle(vehicle) => {
+                        let color = if vehicle.is_sunk {
+                            SUNK_COLOR
+                        } else {
+                            Self::tui_color(vehicle.vehicle_type.color)
+                        };
+                        Self::draw_vehicle(ctx, vehicle, color)
                     }
                 }
-            });
+            }
+            if self.camera.is_following_vehicle() {
+                ctx.print(
+                    x_bounds[0],
+                    y_bounds[0] + row_height,
+                    "Following vehicle (F)",
+                );
+            }
+            if self.is_edit_mode_active() {
+                self.draw_editor(ctx, engine_ref.level());
+                ctx.print(x_bounds[0], y_bounds[1], EDIT_HELP);
+                ctx.print(
+                    x_bounds[0],
+                    y_bounds[1] - row_height,
+                    format!("Material: {}", self.editor.borrow().material().name()),
+                );
+                if !self.message.is_empty() {
+                    ctx.print(
+                        x_bounds[0],
+                        y_bounds[1] - 2.0 * row_height,
+                        Span::styled(self.message.clone(), Style::default().fg(Color::Red)),
+                    );
+                }
+            }
+        });
         frame.render_widget(canvas, area);
     }
 
+    /// Draw the vertices that can be built upon, what the cursor points at, the member that would be built next,
+    /// and the cursor itself
+    fn draw_editor(&self, context: &mut Context, level: &Level) {
+        let editor = self.editor.borrow();
+        let vertices: Vec<(f64, f64)> = level
+            .vertices
+            .iter()
+            .map(|vertex| (vertex.x, vertex.y))
+            .collect();
+        context.draw(&canvas::Points {
+            coords: &vertices,
+            color: Color::White,
+        });
+
+        if let Some(hovered) = editor
+            .hovered_vertex(level)
+            .and_then(|index| level.vertices.get(index.0))
+        {
+            context.draw(&canvas::Points {
+                coords: &[(hovered.x, hovered.y)],
+                color: HOVER_COLOR,
+            });
+        }
+        if let Some(Edge(start, end)) = editor.hovered_member(level) {
+            if let (Some(start), Some(end)) =
+                (level.vertices.get(start.0), level.vertices.get(end.0))
+            {
+                context.draw(&canvas::Line {
+                    x1: start.x,
+                    y1: start.y,
+                    x2: end.x,
+                    y2: end.y,
+                    color: HOVER_COLOR,
+                });
+            }
+        }
+
+        let cursor = editor.cursor();
+        if let Some(selected) = editor
+            .selected_vertex()
+            .and_then(|index| level.vertices.get(index.0))
+        {
+            context.draw(&canvas::Line {
+                x1: selected.x,
+                y1: selected.y,
+                x2: cursor.x,
+                y2: cursor.y,
+                color: Self::material_color(editor.material()),
+            });
+            context.draw(&canvas::Points {
+                coords: &[(selected.x, selected.y)],
+                color: CURSOR_COLOR,
+            });
+        }
+
+        let size = editor::GRID_SIZE / 2.0;
+        for (dx, dy) in [(size, 0.0), (0.0, size)] {
+            context.draw(&canvas::Line {
+                x1: cursor.x - dx,
+                y1: cursor.y - dy,
+                x2: cursor.x + dx,
+                y2: cursor.y + dy,
+                color: CURSOR_COLOR,
+            });
+        }
+    }
+
+    /// The color of members of a material, in the world and in the editor's palette
+    fn material_color(material: Material) -> Color {
+        match material {
+            Material::Road => Color::Black,
+            Material::Wood => Color::Red,
+            Material::Steel => Color::DarkGray,
+            Material::Wire => Color::Gray,
+        }
+    }
+
+    fn tui_color(color: level::Color) -> Color {
+        Color::Rgb(color.red, color.green, color.blue)
+    }
+
+    /// Draw a background line, filling the area below it down to the bottom of the view when needed
+    fn draw_background(
+        context: &mut Context,
+        background: &Background,
+        bottom: f64,
+        dot_width: f64,
+    ) {
+        let color = Self::tui_color(background.color);
+        let line = &background.line;
+        if background.is_filled {
+            let (left, right) = if line.0.x <= line.1.x {
+                (line.0, line.1)
+            } else {
+                (line.1, line.0)
+            };
+            // Fill with a vertical line for every column of dots
+            let mut x = left.x;
+            while x <= right.x {
+                let fraction = if right.x > left.x {
+                    (x - left.x) / (right.x - left.x)
+                } else {
+                    0.0
+                };
+                context.draw(&canvas::Line {
+                    x1: x,
+                    y1: bottom,
+                    x2: x,
+                    y2: left.y + (right.y - left.y) * fraction,
+                    color,
+                });
+                x += dot_width;
+            }
+        }
+        Self::draw_line(context, line, color);
+    }
+
     fn draw_line(context: &mut Context, line: &Line, color: Color) {
         context.draw(&canvas::Line {
             x1: line.0.x,
@@ -86,64 +443,66 @@ impl WorldView {
         });
     }
 
-    fn draw_car(context: &mut Context, position: Coordinates, rotation: f64) {
-        let rotation: euclid::Angle<f64> = euclid::Angle::degrees(rotation);
+    /// Draw a line that hangs down in a parabola, with its lowest point `sag` below the middle of the line
+    fn draw_sagging_line(context: &mut Context, line: &Line, sag: f64, color: Color) {
+        const SEGMENT_COUNT: usize = 8;
+        let point_at = |t: f64| Coordinates {
+            x: line.0.x + (line.1.x - line.0.x) * t,
+            y: line.0.y + (line.1.y - line.0.y) * t - 4.0 * sag * t * (1.0 - t),
+        };
+
+        let points: Vec<Coordinates> = (0..=SEGMENT_COUNT)
+            .map(|i| point_at(i as f64 / SEGMENT_COUNT as f64))
+            .collect();
+        for [point_1, point_2] in points.iter().array_windows() {
+            Self::draw_line(context, &Line(*point_1, *point_2), color);
+        }
+    }
+
+    /// Draw a vehicle in the shape of its type, wheels first so the body is drawn over them
+    fn draw_vehicle(context: &mut Context, vehicle: &Vehicle, color: Color) {
+        /// How many straight lines a wheel is drawn with
+        const WHEEL_SEGMENT_COUNT: usize = 8;
+
+        let rotation: euclid::Angle<f64> = euclid::Angle::degrees(vehicle.rotation);
         let translation: euclid::Vector2D<f64, WorldSpace> =
-            euclid::Vector2D::new(position.x, position.y);
+            euclid::Vector2D::new(vehicle.position.x, vehicle.position.y);
         let world_transformation: VehiclePosition =
             euclid::Transform2D::rotation(rotation).then_translate(translation);
+        let vehicle_type = vehicle.vehicle_type;
 
-        let car_body: Vec<VehiclePoint> = vec![
-            euclid::point2(-0.1, 0.1),
-            euclid::point2(0.1, 0.1),
-            euclid::point2(0.1, 0.2),
-            euclid::point2(0.04, 0.2),
-            euclid::point2(0.0, 0.3),
-            euclid::point2(-0.08, 0.3),
-            euclid::point2(-0.1, 0.2),
-            euclid::point2(-0.1, 0.1),
-        ];
-
-        let tire: Vec<VehiclePoint> = vec![
-            euclid::point2(-0.02, 0.1),
-            euclid::point2(0.0, 0.0),
-            euclid::point2(0.02, 0.1),
-        ];
-
-        let tires: Vec<euclid::Translation2D<f64, VehicleSpace, VehicleSpace>> = vec![
-            euclid::Translation2D::new(-0.08, 0.0),
-            euclid::Translation2D::new(0.08, 0.0),
-        ];
-
-        // Draw tires first
-        for tire_location in tires {
-            for [tire_point_1, tire_point_2] in tire.iter().array_windows() {
-                let tire_world_point_1 = world_transformation
-                    .transform_point(tire_location.transform_point(*tire_point_1));
-                let tire_world_point_2 = world_transformation
-                    .transform_point(tire_location.transform_point(*tire_point_2));
-
+        // Outlines are closed by returning to their first point
+        let mut draw_outline = |outline: Vec<VehiclePoint>, color: Color| {
+            for [point_1, point_2] in outline.iter().chain(outline.first()).array_windows() {
+                let world_point_1 = world_transformation.transform_point(*point_1);
+                let world_point_2 = world_transformation.transform_point(*point_2);
                 context.draw(&canvas::Line {
-                    x1: tire_world_point_1.x,
-                    y1: tire_world_point_1.y,
-                    x2: tire_world_point_2.x,
-                    y2: tire_world_point_2.y,
-                    color: Color::DarkGray,
+                    x1: world_point_1.x,
+                    y1: world_point_1.y,
+                    x2: world_point_2.x,
+                    y2: world_point_2.y,
+                    color,
                 });
             }
-        }
+        };
 
-        // Then overdraw the car body
-        for [point_1, point_2] in car_body.iter().array_windows() {
-            let world_point_1 = world_transformation.transform_point(*point_1);
-            let world_point_2 = world_transformation.transform_point(*point_2);
-            context.draw(&canvas::Line {
-                x1: world_point_1.x,
-                y1: world_point_1.y,
-                x2: world_point_2.x,
-                y2: world_point_2.y,
-                color: Color::LightYellow,
-            });
+        let radius = vehicle_type.wheel_radius;
+        for wheel in &vehicle_type.wheel_positions {
+            let wheel_outline = (0..WHEEL_SEGMENT_COUNT)
+                .map(|i| {
+                    let angle = i as f64 / WHEEL_SEGMENT_COUNT as f64 * std::f64::consts::TAU;
+                    euclid::point2(wheel + radius * angle.cos(), radius + radius * angle.sin())
+                })
+                .collect();
+            draw_outline(wheel_outline, Color::DarkGray);
         }
+        draw_outline(
+            vehicle_type
+                .body
+                .iter()
+                .map(|point| euclid::point2(point.x, point.y))
+                .collect(),
+            color,
+        );
     }
 }
@@ -0,0 +1,493 @@
+#[cfg(test)]
+mod unittest;
+
+use crate::level::{Background, Bridge, Coordinates, Edge, Level, Solution, VertexIndex};
+use crate::material::{Material, MaterialProperties};
+use crate::savefile;
+use crate::vehicle_types;
+use anyhow::{Context, Result};
+use json::JsonValue;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+
+/// How far anchors and vehicles may be from the surface they should be on
+const SURFACE_TOLERANCE: f64 = 0.05;
+
+/// Put in front of the paths of problems that are in the solution file instead of the level file
+const SOLUTION_PATH_PREFIX: &str = "solution:";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Severity {
+    /// The level can't be loaded or simulated
+    Error,
+    /// The level works, but probably not as its author intended
+    Warning,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem found in a level
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// Where the problem is in the level file, such as `$.foreground.road[1][0]`,
+    /// or in the solution file, such as `solution:$.bridge.members.wood[0]`
+    pub path: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Every problem found in a level
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// A straight line between two points, like a road or a piece of terrain
+#[derive(Debug, Clone)]
+struct Segment(Coordinates, Coordinates);
+
+/// The vertex and member data that the semantic checks need
+#[derive(Default)]
+struct Foreground {
+    vertices: Vec<Coordinates>,
+    /// Where every vertex is, the vertices of a solution continue after those of its level
+    vertex_paths: Vec<String>,
+    is_vertex_used: Vec<bool>,
+    /// The first path every member was found at, by its vertices in ascending order
+    member_paths: HashMap<(usize, usize), String>,
+    roads: Vec<Segment>,
+}
+
+impl Foreground {
+    fn add_vertices(&mut self, path: &str, vertices: &[Coordinates]) {
+        for (index, vertex) in vertices.iter().enumerate() {
+            self.vertices.push(vertex.clone());
+            self.vertex_paths.push(format!("{}[{}]", path, index));
+            self.is_vertex_used.push(false);
+        }
+    }
+}
+
+/// Validate a level file, together with a solution file for it if specified
+pub fn load(
+    level_path: &std::path::PathBuf,
+    solution_path: Option<&std::path::PathBuf>,
+) -> Result<Report> {
+    let (level_string, level_json) = read_json(level_path)?;
+    let Some(solution_path) = solution_path else {
+        return Ok(validate(&level_json));
+    };
+    let (_, solution_json) = read_json(solution_path)?;
+    Ok(validate_with_solution(
+        &level_json,
+        savefile::content_hash(&level_string),
+        &solution_json,
+    ))
+}
+
+fn read_json(path: &std::path::PathBuf) -> Result<(String, JsonValue)> {
+    let json_string = fs::read_to_string(path)
+        .with_context(|| format!("Could not read file `{}`", path.display()))?;
+
+    let json_data = json::parse(&json_string)
+        .with_context(|| format!("Could not parse as json `{}`", path.display()))?;
+    Ok((json_string, json_data))
+}
+
+/// Check a level for every problem that can be found without simulating it
+pub fn validate(root_object: &JsonValue) -> Report {
+    let mut report = Report::default();
+    if let Some(level) = report.parse_level(root_object) {
+        report.validate_level(root_object, &level, None);
+    }
+    report
+}
+
+/// Check a level and a solution made for it, the solution needs to be made for a level file with the specified content hash
+pub fn validate_with_solution(
+    level_object: &JsonValue,
+    level_hash: u64,
+    solution_object: &JsonValue,
+) -> Report {
+    let mut report = Report::default();
+    let Some(level) = report.parse_level(level_object) else {
+        return report;
+    };
+    let solution = match savefile::parse_solution(solution_object) {
+        Ok(solution) => solution,
+        Err(error) => {
+            report.parse_error(SOLUTION_PATH_PREFIX, &error);
+            report.validate_level(level_object, &level, None);
+            return report;
+        }
+    };
+    if solution.level_hash != level_hash {
+        report.error(
+            format!("{}$.level.hash", SOLUTION_PATH_PREFIX),
+            format!(
+                "Solution was made for a different version of level `{}`",
+                solution.level_path.display()
+            ),
+        );
+    }
+    report.validate_level(level_object, &level, Some((solution_object, &solution)));
+    report
+}
+
+impl Report {
+    /// Whether the level has no errors, warnings are allowed
+    pub fn is_valid(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.severity != Severity::Error)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for diagnostic in &self.diagnostics {
+            let _ = writeln!(
+                text,
+                "{}: {}: {}",
+                diagnostic.severity, diagnostic.path, diagnostic.message
+            );
+        }
+        let error_count = self.count(Severity::Error);
+        let warning_count = self.count(Severity::Warning);
+        if error_count + warning_count == 0 {
+            let _ = writeln!(text, "No problems found");
+        } else {
+            let _ = writeln!(text, "{} errors, {} warnings", error_count, warning_count);
+        }
+        text
+    }
+
+    pub fn to_json(&self) -> JsonValue {
+        json::object! {
+            valid: self.is_valid(),
+            diagnostics: self
+                .diagnostics
+                .iter()
+                .map(|diagnostic| {
+                    json::object! {
+                        path: diagnostic.path.as_str(),
+                        severity: diagnostic.severity.to_string(),
+                        message: diagnostic.message.as_str(),
+                    }
+                })
+                .collect::<Vec<JsonValue>>(),
+        }
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    }
+
+    fn error(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.add(path.into(), Severity::Error, message.into());
+    }
+
+    fn warning(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.add(path.into(), Severity::Warning, message.into());
+    }
+
+    fn add(&mut self, path: String, severity: Severity, message: String) {
+        self.diagnostics.push(Diagnostic {
+            path,
+            severity,
+            message,
+        });
+    }
+
+    /// Report the errors of the sections that could not be parsed, returns the level if all of them could
+    fn parse_level(&mut self, root_object: &JsonValue) -> Option<Level> {
+        let (level, errors) = savefile::parse_level_sections(root_object);
+        for error in &errors {
+            self.parse_error("", error);
+        }
+        errors.is_empty().then_some(level)
+    }
+
+    /// Report a parse error at the path of the value it was found in
+    fn parse_error(&mut self, path_prefix: &str, error: &anyhow::Error) {
+        let (path, message) = savefile::locate_error(error);
+        self.error(format!("{}{}", path_prefix, path), message);
+    }
+
+    /// Check the parts of a parsed level that depend on each other, and those of a solution for it if any
+    fn validate_level(
+        &mut self,
+        level_object: &JsonValue,
+        level: &Level,
+        solution: Option<(&JsonValue, &Solution)>,
+    ) {
+        if let Some(water) = &level.background.water {
+            if level.failure.water_level != Some(water.level) {
+                self.warning(
+                    "$.background.water.level",
+                    format!(
+                        "Water is drawn at a height of {}, but objects only sink below the failure water level",
+                        water.level
+                    ),
+                );
+            }
+        }
+
+        let mut foreground = Foreground::default();
+        foreground.add_vertices("$.foreground.vertices", &level.vertices);
+        let terrain = Self::terrain_segments(&level.background);
+        self.validate_anchors(&level.anchors, &terrain, &mut foreground);
+        self.validate_members("$.foreground.road", &level.road, None, &mut foreground);
+        self.validate_bridge(
+            "$.foreground.bridge.members",
+            &level_object["foreground"]["bridge"]["members"],
+            level,
+            &level.bridge,
+            &mut foreground,
+        );
+        if let Some((solution_object, solution)) = solution {
+            let path = format!("{}$.vertices", SOLUTION_PATH_PREFIX);
+            foreground.add_vertices(&path, &solution.vertices);
+            self.validate_bridge(
+                &format!("{}$.bridge.members", SOLUTION_PATH_PREFIX),
+                &solution_object["bridge"]["members"],
+                level,
+                &solution.bridge,
+                &mut foreground,
+            );
+        }
+
+        let vertices = foreground
+            .vertex_paths
+            .iter()
+            .zip(&foreground.is_vertex_used);
+        for (index, (path, is_used)) in vertices.enumerate() {
+            if !is_used {
+                self.warning(
+                    path.as_str(),
+                    format!("Vertex {} is not used by any anchor, road or member", index),
+                );
+            }
+        }
+
+        for (index, vehicle) in level.vehicles.iter().enumerate() {
+            let path = format!("$.foreground.vehicles[{}]", index);
+            if let Err(error) = vehicle_types::find(&vehicle.name) {
+                self.error(format!("{}.type", path), format!("{:#}", error));
+            }
+
+            let position = &vehicle.position;
+            // Vehicles only drive on roads, the terrain just stops them from falling through it
+            let is_on_road = foreground
+                .roads
+                .iter()
+                .any(|segment| Self::is_on_surface(position, segment));
+            if !is_on_road {
+                self.warning(
+                    format!("{}.position", path),
+                    format!(
+                        "Vehicle at ({}, {}) is not on a road, so it will fall",
+                        position.x, position.y
+                    ),
+                );
+            }
+        }
+    }
+
+    fn validate_anchors(
+        &mut self,
+        anchors: &[VertexIndex],
+        terrain: &[Segment],
+        foreground: &mut Foreground,
+    ) {
+        let mut checked_anchors: Vec<usize> = vec![];
+        for (index, anchor) in anchors.iter().enumerate() {
+            let path = format!("$.foreground.anchors[{}]", index);
+            let Some(anchor) = self.vertex_index(&path, anchor, foreground) else {
+                continue;
+            };
+            if checked_anchors.contains(&anchor) {
+                self.warning(path, format!("Vertex {} is anchored twice", anchor));
+                continue;
+            }
+            checked_anchors.push(anchor);
+
+            let position = &foreground.vertices[anchor];
+            let is_on_terrain = terrain.iter().any(|segment| {
+                position.distance_to_segment(&segment.0, &segment.1) <= SURFACE_TOLERANCE
+            });
+            if !terrain.is_empty() && !is_on_terrain {
+                self.warning(
+                    path,
+                    format!(
+                        "Anchor at ({}, {}) is not on the terrain",
+                        position.x, position.y
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Check the members of every material in a bridge, and warn about the materials that are ignored
+    fn validate_bridge(
+        &mut self,
+        path: &str,
+        members_json: &JsonValue,
+        level: &Level,
+        bridge: &Bridge,
+        foreground: &mut Foreground,
+    ) {
+        for (name, _) in members_json.entries() {
+            if Material::from_name(name).is_none() {
+                self.warning(
+                    format!("{}.{}", path, name),
+                    format!("Not a material: {}, its members are ignored", name),
+                );
+            }
+        }
+        for (material, properties) in level.materials.iter() {
+            self.validate_members(
+                &format!("{}.{}", path, material.name()),
+                bridge.members(material),
+                Some((material, properties)),
+                foreground,
+            );
+        }
+    }
+
+    /// Check a list of static roads or bridge members, which are limited by the properties of their material if any
+    fn validate_members(
+        &mut self,
+        path: &str,
+        members: &[Edge],
+        material: Option<(Material, &MaterialProperties)>,
+        foreground: &mut Foreground,
+    ) {
+        for (index, Edge(start, end)) in members.iter().enumerate() {
+            let member_path = format!("{}[{}]", path, index);
+            let start = self.vertex_index(&format!("{}[0]", member_path), start, foreground);
+            let end = self.vertex_index(&format!("{}[1]", member_path), end, foreground);
+            let (Some(start), Some(end)) = (start, end) else {
+                continue;
+            };
+
+            if start == end {
+                self.error(
+                    member_path,
+                    format!("Member connects vertex {} to itself", start),
+                );
+                continue;
+            }
+            let key = (start.min(end), start.max(end));
+            if let Some(original_path) = foreground.member_paths.get(&key) {
+                self.warning(
+                    member_path,
+                    format!(
+                        "Member between vertices {} and {} is a duplicate of {}",
+                        start, end, original_path
+                    ),
+                );
+                continue;
+            }
+            foreground.member_paths.insert(key, member_path.clone());
+
+            let (start_position, end_position) =
+                (&foreground.vertices[start], &foreground.vertices[end]);
+            let length =
+                (end_position.x - start_position.x).hypot(end_position.y - start_position.y);
+            if length <= f64::EPSILON {
+                self.error(
+                    member_path,
+                    format!(
+                        "Member between vertices {} and {} has zero length",
+                        start, end
+                    ),
+                );
+                continue;
+            }
+            if let Some((_, properties)) = material {
+                if !properties.is_allowed {
+                    let message =
+                        "Players are not allowed to build with the material of this member";
+                    if member_path.starts_with(SOLUTION_PATH_PREFIX) {
+                        self.error(member_path.as_str(), message);
+                    } else {
+                        // Levels may build with materials that players aren't allowed to use
+                        self.warning(member_path.as_str(), message);
+                    }
+                }
+                if length > properties.max_length {
+                    self.error(
+                        member_path.as_str(),
+                        format!(
+                            "Member between vertices {} and {} is {:.2} long, which is longer than the maximum length of {}",
+                            start, end, length, properties.max_length
+                        ),
+                    );
+                }
+            }
+            if matches!(material, None | Some((Material::Road, _))) {
+                let segment = Segment(start_position.clone(), end_position.clone());
+                foreground.roads.push(segment);
+            }
+        }
+    }
+
+    /// Check that a vertex index refers to an existing vertex, and mark that vertex as used
+    fn vertex_index(
+        &mut self,
+        path: &str,
+        index: &VertexIndex,
+        foreground: &mut Foreground,
+    ) -> Option<usize> {
+        let VertexIndex(index) = *index;
+        let Some(is_used) = foreground.is_vertex_used.get_mut(index) else {
+            self.error(
+                path,
+                format!(
+                    "Vertex {} does not exist, there are {} vertices",
+                    index,
+                    foreground.vertices.len()
+                ),
+            );
+            return None;
+        };
+        *is_used = true;
+        Some(index)
+    }
+
+    fn terrain_segments(background: &Background) -> Vec<Segment> {
+        background
+            .terrain
+            .iter()
+            .flat_map(|polyline| {
+                polyline
+                    .points
+                    .windows(2)
+                    .map(|pair| Segment(pair[0].clone(), pair[1].clone()))
+            })
+            .collect()
+    }
+
+    /// Whether a vehicle at the point would be standing on the surface, the same way vehicles find roads to drive on
+    fn is_on_surface(point: &Coordinates, segment: &Segment) -> bool {
+        let Segment(start, end) = segment;
+        let dx = end.x - start.x;
+        if dx.abs() <= f64::EPSILON {
+            return false;
+        }
+        let fraction = (point.x - start.x) / dx;
+        let surface_y = start.y + (end.y - start.y) * fraction;
+        (0.0..=1.0).contains(&fraction) && (surface_y - point.y).abs() <= SURFACE_TOLERANCE
+    }
+}
@@ -0,0 +1,339 @@
+use crate::validation::{validate, validate_with_solution, Diagnostic, Severity};
+
+/// Validate a level and return its problems as (path, severity) pairs, which are easier to compare than messages
+fn problems(level: &str) -> Vec<(String, Severity)> {
+    validate(&json::parse(level).unwrap())
+        .diagnostics
+        .into_iter()
+        .map(|diagnostic| (diagnostic.path, diagnostic.severity))
+        .collect()
+}
+
+fn error(path: &str) -> (String, Severity) {
+    (path.to_string(), Severity::Error)
+}
+
+fn warning(path: &str) -> (String, Severity) {
+    (path.to_string(), Severity::Warning)
+}
+
+#[test]
+fn valid_level_test() {
+    let report = validate(
+        &json::parse(
+            r##"{
+              "version": 1,
+              "background": {"terrain": [{"points": [[-1, 1], [0, 1]], "color": "#000000"}]},
+              "rules": {"goal": {"finish_x": 2}},
+              "foreground": {
+                "vertices": [[0, 1], [1, 1], [2, 1]],
+                "anchors": [0],
+                "road": [[0, 1]],
+                "bridge": {"members": {"road": [[1, 2]]}},
+                "vehicles": [{"type": "car", "position": [0.5, 1], "rotation": 0}]
+              }
+            }"##,
+        )
+        .unwrap(),
+    );
+    assert_eq!(report.diagnostics, vec![]);
+    assert!(report.is_valid());
+    assert_eq!(report.to_text(), "No problems found\n");
+}
+
+#[test]
+fn unsupported_version_test() {
+    assert_eq!(
+        problems(r#"{"version": 99, "foreground": {}}"#),
+        vec![error("$.version")]
+    );
+}
+
+#[test]
+fn invalid_sections_test() {
+    assert_eq!(
+        problems(
+            r#"{
+              "metadata": {"title": 1},
+              "background": [],
+              "rules": {
+                "materials": {"glass": {}},
+                "goal": {"finish_x": "far"},
+                "failure": {"timeout": "soon"}
+              },
+              "foreground": []
+            }"#
+        ),
+        vec![
+            error("$.metadata.title"),
+            error("$.background"),
+            error("$.rules.materials"),
+            error("$.rules.goal.finish_x"),
+            error("$.rules.failure.timeout"),
+            error("$.foreground"),
+        ]
+    );
+}
+
+#[test]
+fn invalid_foreground_test() {
+    assert_eq!(
+        problems(
+            r#"{
+              "foreground": {
+                "vertices": [[0, 1], [1, 1], [2]],
+                "anchors": [0, -1],
+                "road": [[0, 1], [1, 5], [0]],
+                "bridge": {"members": []}
+              }
+            }"#
+        ),
+        vec![
+            error("$.foreground.vertices[2]"),
+            error("$.foreground.anchors[1]"),
+            error("$.foreground.road[2]"),
+            error("$.foreground.bridge"),
+            error("$.foreground.vehicles"),
+        ]
+    );
+}
+
+#[test]
+fn invalid_vertex_references_test() {
+    assert_eq!(
+        problems(
+            r#"{
+              "foreground": {
+                "vertices": [[0, 1], [1, 1], [2, 1]],
+                "anchors": [0, 3],
+                "road": [[0, 1], [1, 5]],
+                "vehicles": []
+              }
+            }"#
+        ),
+        vec![
+            error("$.foreground.anchors[1]"),
+            error("$.foreground.road[1][1]"),
+            warning("$.foreground.vertices[2]"),
+        ]
+    );
+}
+
+#[test]
+fn invalid_members_test() {
+    let report = validate(
+        &json::parse(
+            r#"{
+              "foreground": {
+                "vertices": [[0, 1], [1, 1], [1, 1], [9, 1]],
+                "road": [[0, 1]],
+                "bridge": {"members": {
+                  "wood": [[1, 0], [2, 2], [1, 2], [0, 3]],
+                  "glass": [[0, 1]]
+                }},
+                "vehicles": []
+              }
+            }"#,
+        )
+        .unwrap(),
+    );
+    assert_eq!(
+        report.diagnostics,
+        vec![
+            Diagnostic {
+                path: "$.foreground.bridge.members.glass".to_string(),
+                severity: Severity::Warning,
+                message: "Not a material: glass, its members are ignored".to_string(),
+            },
+            Diagnostic {
+                path: "$.foreground.bridge.members.wood[0]".to_string(),
+                severity: Severity::Warning,
+                message: "Member between vertices 1 and 0 is a duplicate of $.foreground.road[0]"
+                    .to_string(),
+            },
+            Diagnostic {
+                path: "$.foreground.bridge.members.wood[1]".to_string(),
+                severity: Severity::Error,
+                message: "Member connects vertex 2 to itself".to_string(),
+            },
+            Diagnostic {
+                path: "$.foreground.bridge.members.wood[2]".to_string(),
+                severity: Severity::Error,
+                message: "Member between vertices 1 and 2 has zero length".to_string(),
+            },
+            Diagnostic {
+                path: "$.foreground.bridge.members.wood[3]".to_string(),
+                severity: Severity::Error,
+                message: "Member between vertices 0 and 3 is 9.00 long, which is longer than the maximum length of 2"
+                    .to_string(),
+            },
+        ]
+    );
+    assert!(!report.is_valid());
+    assert!(report.to_text().ends_with("3 errors, 2 warnings\n"));
+}
+
+#[test]
+fn misplaced_anchors_and_vehicles_test() {
+    assert_eq!(
+        problems(
+            r##"{
+              "background": {"terrain": [{"points": [[0, 0], [1, 0]], "color": "#000000"}]},
+              "foreground": {
+                "vertices": [[0, 0], [0, 1], [1, 1]],
+                "anchors": [0, 1],
+                "road": [[1, 2]],
+                "vehicles": [
+                  {"type": "car", "position": [0.5, 1], "rotation": 0},
+                  {"type": "car", "position": [0.5, 0], "rotation": 0},
+                  {"type": "car", "position": [0.5, 0.5], "rotation": 0},
+                  {"type": "boat", "position": [0.5, 1], "rotation": 0}
+                ]
+              }
+            }"##
+        ),
+        vec![
+            warning("$.foreground.anchors[1]"),
+            // Vehicles can't drive on the terrain, only on roads
+            warning("$.foreground.vehicles[1].position"),
+            warning("$.foreground.vehicles[2].position"),
+            error("$.foreground.vehicles[3].type"),
+        ]
+    );
+}
+
+#[test]
+fn report_to_json_test() {
+    let report = validate(
+        &json::parse(
+            r#"{"foreground": {"vertices": [[0, 1], [1, 1]], "road": [[0, 0]], "vehicles": []}}"#,
+        )
+        .unwrap(),
+    );
+    assert_eq!(
+        report.to_json(),
+        json::object! {
+            valid: false,
+            diagnostics: [
+                {
+                    path: "$.foreground.road[0]",
+                    severity: "error",
+                    message: "Member connects vertex 0 to itself",
+                },
+                {
+                    path: "$.foreground.vertices[1]",
+                    severity: "warning",
+                    message: "Vertex 1 is not used by any anchor, road or member",
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn forbidden_materials_test() {
+    assert_eq!(
+        problems(
+            r#"{
+              "version": 1,
+              "rules": {"materials": {"steel": {"allowed": false}}},
+              "foreground": {
+                "vertices": [[0, 1], [1, 1], [1, 2]],
+                "bridge": {"members": {"steel": [[0, 2]], "wood": [[0, 1], [1, 2]]}},
+                "vehicles": []
+              }
+            }"#
+        ),
+        vec![warning("$.foreground.bridge.members.steel[0]")]
+    );
+}
+
+#[test]
+fn water_level_test() {
+    let level = |failure_water_level: &str| {
+        format!(
+            r##"{{
+              "version": 1,
+              "background": {{"water": {{"level": 0, "color": "#0000ff"}}}},
+              "rules": {{"failure": {{"water_level": {}}}}},
+              "foreground": {{"vertices": [], "vehicles": []}}
+            }}"##,
+            failure_water_level
+        )
+    };
+    assert_eq!(problems(&level("0")), vec![]);
+    // Objects would sink in different water than the water that is drawn
+    assert_eq!(
+        problems(&level("-1")),
+        vec![warning("$.background.water.level")]
+    );
+}
+
+#[test]
+fn solution_test() {
+    let level = json::parse(
+        r#"{
+          "foreground": {
+            "vertices": [[0, 1], [1, 1]],
+            "road": [[0, 1]],
+            "vehicles": [{"type": "car", "position": [0.5, 1], "rotation": 0}]
+          }
+        }"#,
+    )
+    .unwrap();
+    let problems = |level_hash: u64, solution: &str| -> Vec<(String, Severity)> {
+        validate_with_solution(&level, level_hash, &json::parse(solution).unwrap())
+            .diagnostics
+            .into_iter()
+            .map(|diagnostic| (diagnostic.path, diagnostic.severity))
+            .collect()
+    };
+
+    let solution = r#"{
+      "level": {"path": "level.json", "hash": "00000000000000ff"},
+      "vertices": [[1, 2], [1, 9], [2, 2]],
+      "bridge": {"members": {"wood": [[1, 2], [2, 3], [0, 0]], "glass": [], "wire": [[1, 6]]}}
+    }"#;
+    assert_eq!(
+        problems(0xff, solution),
+        vec![
+            warning("solution:$.bridge.members.glass"),
+            error("solution:$.bridge.members.wood[1]"),
+            error("solution:$.bridge.members.wood[2]"),
+            error("solution:$.bridge.members.wire[0][1]"),
+            warning("solution:$.vertices[2]"),
+        ]
+    );
+    // The level is still checked when the solution can't be parsed, or was made for a different level
+    assert_eq!(
+        problems(
+            0xff,
+            r#"{"level": {"path": "level.json", "hash": "ff"}, "vertices": [[1]]}"#
+        ),
+        vec![error("solution:$.vertices[0]")]
+    );
+    assert_eq!(
+        problems(
+            0,
+            r#"{"level": {"path": "level.json", "hash": "ff"}, "vertices": []}"#
+        ),
+        vec![error("solution:$.level.hash")]
+    );
+}
+
+#[test]
+fn vehicle_on_terrain_test() {
+    assert_eq!(
+        problems(
+            r##"{
+              "background": {"terrain": [{"points": [[-5, 0], [5, 0]], "color": "#000000"}]},
+              "foreground": {
+                "vertices": [[0, 1], [1, 1]],
+                "road": [[0, 1]],
+                "vehicles": [{"type": "car", "position": [3, 0], "rotation": 0}]
+              }
+            }"##
+        ),
+        vec![warning("$.foreground.vehicles[0].position")]
+    );
+}
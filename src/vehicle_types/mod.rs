@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod unittest;
+
+use crate::level::{Color, Coordinates};
+use crate::savefile;
+use anyhow::{anyhow, ensure, Context, Result};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Where the vehicle types that levels can use are loaded from, relative to the working directory.
+/// Adding a type to this file makes it available to every level that is played interactively.
+pub const VEHICLE_TYPES_PATH: &str = "vehicles.json";
+
+/// The vehicle types that are used when there is no vehicle types file
+const DEFAULT_VEHICLE_TYPES_JSON: &str = include_str!("../../vehicles.json");
+
+static VEHICLE_TYPES: OnceLock<Vec<VehicleType>> = OnceLock::new();
+
+/// The shape and physical properties of a kind of vehicle
+#[derive(Debug, PartialEq, Clone)]
+pub struct VehicleType {
+    pub name: String,
+    pub mass: f64,
+    /// Driving speed in world units per second
+    pub speed: f64,
+    pub color: Color,
+    /// The outline of the vehicle, relative to the point between the wheels where they touch the road
+    pub body: Vec<Coordinates>,
+    /// The horizontal positions of the wheels, relative to the same point
+    pub wheel_positions: Vec<f64>,
+    pub wheel_radius: f64,
+}
+
+impl VehicleType {
+    /// The horizontal position of the rearmost wheel
+    pub fn rear_wheel(&self) -> f64 {
+        self.wheel_positions
+            .iter()
+            .copied()
+            .fold(f64::MAX, f64::min)
+    }
+
+    /// The horizontal position of the frontmost wheel
+    pub fn front_wheel(&self) -> f64 {
+        self.wheel_positions
+            .iter()
+            .copied()
+            .fold(f64::MIN, f64::max)
+    }
+}
+
+/// Find a vehicle type by the name levels refer to it with.
+/// The vehicle types are loaded on the first call.
+pub fn find(name: &str) -> Result<&'static VehicleType> {
+    vehicle_types()?
+        .iter()
+        .find(|vehicle_type| vehicle_type.name == name)
+        .ok_or_else(|| anyhow!("Not a vehicle type: {}", name))
+}
+
+/// Use only the vehicle types the game was built with, even when there is a vehicle types file.
+/// Headless simulations and replays use them, so their results don't depend on the working directory.
+/// This needs to happen before the first vehicle type is found.
+pub fn use_built_in() -> Result<()> {
+    let vehicle_types = built_in()?;
+    VEHICLE_TYPES
+        .set(vehicle_types)
+        .map_err(|_| anyhow!("Vehicle types were already loaded"))
+}
+
+/// The loaded vehicle types, loading them from the vehicle types file when they weren't yet
+fn vehicle_types() -> Result<&'static Vec<VehicleType>> {
+    if let Some(vehicle_types) = VEHICLE_TYPES.get() {
+        return Ok(vehicle_types);
+    }
+    let path = Path::new(VEHICLE_TYPES_PATH);
+    let vehicle_types = if path.exists() {
+        load(path)?
+    } else {
+        built_in()?
+    };
+    Ok(VEHICLE_TYPES.get_or_init(|| vehicle_types))
+}
+
+fn built_in() -> Result<Vec<VehicleType>> {
+    parse_vehicle_types(&json::parse(DEFAULT_VEHICLE_TYPES_JSON)?)
+        .context("Could not parse the default vehicle types")
+}
+
+/// Load vehicle types from a file
+pub fn load(path: &Path) -> Result<Vec<VehicleType>> {
+    let json_string = fs::read_to_string(path)
+        .with_context(|| format!("Could not read file `{}`", path.display()))?;
+    let json_data = json::parse(&json_string)
+        .with_context(|| format!("Could not parse as json `{}`", path.display()))?;
+    parse_vehicle_types(&json_data)
+        .with_context(|| format!("Could not parse vehicle types `{}`", path.display()))
+}
+
+fn parse_vehicle_types(root_object: &json::JsonValue) -> Result<Vec<VehicleType>> {
+    ensure!(
+        root_object.is_object(),
+        "Vehicle types should be an object, instead found {}",
+        root_object
+    );
+    root_object
+        .entries()
+        .map(|(name, vehicle_type_json)| {
+            parse_vehicle_type(name, vehicle_type_json)
+                .with_context(|| format!("Could not parse vehicle type {}", name))
+        })
+        .collect()
+}
+
+fn parse_vehicle_type(name: &str, root_object: &json::JsonValue) -> Result<VehicleType> {
+    let positive_number = |key: &str| -> Result<f64> {
+        let value = root_object[key]
+            .as_f64()
+            .with_context(|| format!("Vehicle {} should be a number", key))?;
+        ensure!(
+            value > 0.0,
+            "Vehicle {} should be positive, instead found {}",
+            key,
+            value
+        );
+        Ok(value)
+    };
+
+    let body_json = &root_object["body"];
+    ensure!(
+        body_json.is_array() && body_json.len() >= 2,
+        "Vehicle body should be an array of at least two coordinates, instead found {}",
+        body_json
+    );
+    let wheels_json = &root_object["wheels"];
+    let positions_json = &wheels_json["positions"];
+    ensure!(
+        positions_json.is_array() && !positions_json.is_empty(),
+        "Vehicle wheel positions should be a non-empty array, instead found {}",
+        positions_json
+    );
+    let wheel_radius = wheels_json["radius"]
+        .as_f64()
+        .filter(|radius| *radius > 0.0)
+        .with_context(|| {
+            format!(
+                "Vehicle wheel radius should be a positive number, instead found {}",
+                wheels_json["radius"]
+            )
+        })?;
+
+    Ok(VehicleType {
+        name: name.to_string(),
+        mass: positive_number("mass")?,
+        speed: positive_number("speed")?,
+        color: savefile::parse_color(&root_object["color"])?,
+        body: body_json
+            .members()
+            .map(savefile::parse_coordinates)
+            .collect::<Result<Vec<Coordinates>>>()?,
+        wheel_positions: positions_json
+            .members()
+            .map(|position| {
+                position.as_f64().with_context(|| {
+                    format!(
+                        "Wheel position should be a number, instead found {}",
+                        position
+                    )
+                })
+            })
+            .collect::<Result<Vec<f64>>>()?,
+        wheel_radius,
+    })
+}
@@ -0,0 +1,87 @@
+use crate::level::{Color, Coordinates};
+use crate::vehicle_types::{
+    find, load, parse_vehicle_types, VehicleType, DEFAULT_VEHICLE_TYPES_JSON, VEHICLE_TYPES_PATH,
+};
+use std::path::Path;
+
+#[test]
+fn find_test() {
+    let car = find("car").unwrap();
+    assert_eq!(car.name, "car");
+    assert_eq!(car.rear_wheel(), -0.08);
+    assert_eq!(car.front_wheel(), 0.08);
+    assert!(find("bus").unwrap().mass > car.mass);
+    assert!(find("boat").is_err());
+}
+
+#[test]
+fn parse_vehicle_types_test() {
+    assert_eq!(
+        parse_vehicle_types(
+            &json::parse(
+                r##"{
+                  "truck": {
+                    "mass": 5,
+                    "speed": 0.3,
+                    "color": "#ff0000",
+                    "body": [[-0.3, 0.1], [0.3, 0.1], [0.3, 0.4]],
+                    "wheels": {"positions": [-0.2, 0.0, 0.2], "radius": 0.1}
+                  }
+                }"##
+            )
+            .unwrap()
+        )
+        .unwrap(),
+        vec![VehicleType {
+            name: "truck".to_string(),
+            mass: 5.0,
+            speed: 0.3,
+            color: Color {
+                red: 255,
+                green: 0,
+                blue: 0
+            },
+            body: vec![
+                Coordinates { x: -0.3, y: 0.1 },
+                Coordinates { x: 0.3, y: 0.1 },
+                Coordinates { x: 0.3, y: 0.4 },
+            ],
+            wheel_positions: vec![-0.2, 0.0, 0.2],
+            wheel_radius: 0.1,
+        }]
+    );
+
+    // Test invalid values
+    let valid = json::object! {
+        mass: 1,
+        speed: 1,
+        color: "#000000",
+        body: [[0, 0], [1, 0]],
+        wheels: {positions: [0], radius: 0.1},
+    };
+    assert!(parse_vehicle_types(&json::array![valid.clone()]).is_err());
+    for (key, value) in [
+        ("mass", json::JsonValue::from(0)),
+        ("speed", "fast".into()),
+        ("color", "red".into()),
+        ("body", json::array![[0, 0]]),
+        ("wheels", json::object! {positions: [], radius: 0.1}),
+        ("wheels", json::object! {positions: [0], radius: -1}),
+    ] {
+        let mut invalid = valid.clone();
+        invalid[key] = value;
+        assert!(parse_vehicle_types(&json::object! {invalid: invalid}).is_err());
+    }
+    assert!(parse_vehicle_types(&json::object! {valid: valid}).is_ok());
+}
+
+#[test]
+fn load_test() {
+    // The shipped file is the same as the default vehicle types
+    let vehicle_types = load(Path::new(VEHICLE_TYPES_PATH)).unwrap();
+    assert_eq!(
+        vehicle_types,
+        parse_vehicle_types(&json::parse(DEFAULT_VEHICLE_TYPES_JSON).unwrap()).unwrap()
+    );
+    assert!(load(Path::new("vehicles/missing.json")).is_err());
+}